@@ -1,11 +1,9 @@
-use std::fs;
-
 use rustc_hash::FxHashSet;
 
 fn main() {
-    for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
+    for part1 in [true, false] {
         let mut santa_x = 0i64;
         let mut santa_y = 0i64;
         let mut robo_x = 0i64;
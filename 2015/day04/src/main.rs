@@ -1,5 +1,4 @@
 use std::{
-    fs,
     sync::atomic::{AtomicI64, Ordering},
     thread,
 };
@@ -7,7 +6,7 @@ use std::{
 const BLOCK_SIZE: i64 = 1000;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let input = input.trim();
     let i = AtomicI64::new(0);
     let min1 = AtomicI64::new(i64::MAX);
@@ -1,9 +1,7 @@
-use std::fs;
-
 fn main() {
-    for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
+    for part1 in [true, false] {
         let mut nice = 0;
         for l in input.lines() {
             if part1 {
@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Operand {
     Literal(u16),
@@ -61,7 +59,7 @@ fn parse_operand(o: &str) -> Operand {
 fn main() {
     let mut wires = vec![Instruction::Unknown; 27 * 27];
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     for l in input.lines() {
         let (instr, output) = l.split_once(" -> ").unwrap();
         let parts = instr.split_ascii_whitespace().collect::<Vec<_>>();
@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Computes the next permutation of a bitmask where `k` out of `n` bits are
 /// set, in lexicographical order. For example, if `k` is 3 and the current
 /// bitmask is 00010011, the next items would be 00010101, 00010110, 00011001,
@@ -78,7 +76,7 @@ fn find(distances: &[(&str, &str, u64)], n_cities: usize, min: bool) -> u64 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // parse input and create list of all distances between all cities
     let mut distances = Vec::new();
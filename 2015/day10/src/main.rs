@@ -1,5 +1,3 @@
-use std::fs;
-
 fn look_and_say(s: Vec<u8>) -> Vec<u8> {
     let mut result = Vec::new();
 
@@ -20,7 +18,7 @@ fn look_and_say(s: Vec<u8>) -> Vec<u8> {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut bytes = input.trim().bytes().map(|b| b - b'0').collect::<Vec<_>>();
 
     // part 1
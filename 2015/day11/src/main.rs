@@ -1,5 +1,3 @@
-use std::fs;
-
 fn is_valid(password: &[u8]) -> bool {
     let mut repeats = 0;
     let mut i = 0;
@@ -54,7 +52,7 @@ fn next(password: Vec<u8>) -> Vec<u8> {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let n1 = next(input.trim().bytes().rev().collect());
     println!(
         "{}",
@@ -1,9 +1,7 @@
-use std::fs;
-
 use actson::{JsonEvent, JsonParser, feeder::SliceJsonFeeder};
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let feeder = SliceJsonFeeder::new(input.as_bytes());
     let mut parser = JsonParser::new(feeder);
@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Computes the next permutation of a bitmask where `k` out of `n` bits are
 /// set, in lexicographical order. For example, if `k` is 3 and the current
 /// bitmask is 00010011, the next items would be 00010101, 00010110, 00011001,
@@ -81,7 +79,7 @@ fn main() {
     // can skip ME in part 1 by passing `n_people-1` to `find`
     const ME: &str = "\x7f";
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // parse input and create list of all gains and losses
     let mut gains = Vec::new();
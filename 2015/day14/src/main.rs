@@ -1,5 +1,3 @@
-use std::fs;
-
 #[inline]
 fn dist(i: usize, r: (&str, usize, usize, usize)) -> usize {
     let f = r.2 + r.3;
@@ -7,7 +5,7 @@ fn dist(i: usize, r: (&str, usize, usize, usize)) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let reindeer = input
         .lines()
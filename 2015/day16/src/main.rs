@@ -1,5 +1,3 @@
-use std::fs;
-
 const WHAT_I_REMEMBER: [i32; 10] = [
     3, // children
     7, // cats
@@ -28,7 +26,7 @@ enum Properties {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let sues = input.lines().map(|l| {
         let (_, attrs) = l.split_once(": ").unwrap();
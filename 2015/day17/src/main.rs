@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Count the number of ways to fit exactly `remaining` liters of eggnogs into
 /// the containers from index `i` on, while `used` containers have already been
 /// used. Also returns the minimum number of containers necessary. Can be
@@ -48,7 +46,7 @@ fn dfs(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut containers = input
         .lines()
         .map(|c| c.parse::<usize>().unwrap())
@@ -1,7 +1,5 @@
-use std::fs;
-
 #[cfg(feature = "visualize")]
-use screen::Screen;
+use screen::{Options, PixelMode, Screen};
 
 /// Rows and columns
 const H: usize = 100;
@@ -39,7 +37,12 @@ fn run<'a>(
     keep_corners: bool,
 ) -> u32 {
     #[cfg(feature = "visualize")]
-    let mut screen = Screen::new(H, H / 2, 15);
+    let mut screen = Options::from_env()
+        .expect("Invalid visualization options")
+        .map(|options| {
+            let (width, height) = PixelMode::HalfBlock.screen_size(H, H);
+            Screen::with_options(width, height, 15, &options).expect("Unable to create screen")
+        });
 
     if keep_corners {
         set_corner_bits(grid);
@@ -94,27 +97,22 @@ fn run<'a>(
 }
 
 #[cfg(feature = "visualize")]
-fn visualize(grid: &mut [u64; (H + 2) * W], screen: &mut Screen) {
-    let mut screen_grid = vec!['.'; H / 2 * H];
-    for y in 0..H / 2 {
+fn visualize(grid: &mut [u64; (H + 2) * W], screen: &mut Option<Screen>) {
+    let Some(screen) = screen else {
+        return;
+    };
+    let mut pixels = vec![false; H * H];
+    for y in 0..H {
         for x in 0..H {
             let mask: u64 = 1 << ((x % CPW) * BPC);
-            let t = grid[y * 2 * W + x / CPW] & mask > 0;
-            let b = grid[(y * 2 + 1) * W + x / CPW] & mask > 0;
-            let c = match (t, b) {
-                (true, true) => '█',
-                (true, false) => '▀',
-                (false, true) => '▄',
-                (false, false) => ' ',
-            };
-            screen_grid[y * H + x] = c;
+            pixels[y * H + x] = grid[y * W + x / CPW] & mask > 0;
         }
     }
-    screen.update(screen_grid);
+    screen.update_pixels(&pixels, H, PixelMode::HalfBlock);
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // add an empty row at the top and one at the bottom
     let mut grid: [u64; (H + 2) * W] = [0; (H + 2) * W];
@@ -1,7 +1,5 @@
-use std::fs;
-
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let min_presents = input.trim().parse::<usize>().unwrap();
 
     // Part 1: Pre-compute divisor sums up to 1 million. This limit was enough
@@ -1,5 +1,3 @@
-use std::fs;
-
 const WEAPONS: [(i32, i32, i32); 5] = [(8, 4, 0), (10, 5, 0), (25, 6, 0), (40, 7, 0), (74, 8, 0)];
 
 const ARMOR: [(i32, i32, i32); 5] = [(13, 0, 1), (31, 0, 2), (53, 0, 3), (75, 0, 4), (102, 0, 5)];
@@ -110,7 +108,7 @@ mod game {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut boss_stats = input.lines().map(|l| {
         let p = l.split_once(": ").unwrap();
         p.1.parse::<i32>().unwrap()
@@ -1,4 +1,4 @@
-use std::{cmp::Reverse, collections::BinaryHeap, fs, hash::Hash};
+use std::{cmp::Reverse, collections::BinaryHeap, hash::Hash};
 
 use rustc_hash::FxHashSet;
 
@@ -62,7 +62,7 @@ fn evaluate_effects(mut s: GameState) -> GameState {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut boss_stats = input.lines().map(|l| {
         let p = l.split_once(": ").unwrap();
         p.1.parse::<i32>().unwrap()
@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy)]
 enum Instruction {
     Hlf(usize),
@@ -11,7 +9,7 @@ enum Instruction {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let instructions = input
         .lines()
         .map(|i| {
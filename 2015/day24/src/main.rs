@@ -1,5 +1,3 @@
-use std::fs;
-
 fn main() {
     // After solving this puzzle differently (see previous Git commit), I
     // noticed that the shortest possible combination to fill the first group
@@ -11,7 +9,7 @@ fn main() {
     // have found the combination with the least packages and the lowest quantum
     // entanglement.
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let packages = input
         .lines()
         .map(|p| p.parse::<usize>().unwrap())
@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Calculate `a.pow(b) % m` using binary exponentiation
 fn pow_mod(mut a: u64, mut b: u64, m: u64) -> u64 {
     let mut result = 1;
@@ -16,7 +14,7 @@ fn pow_mod(mut a: u64, mut b: u64, m: u64) -> u64 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut parts = input.split_ascii_whitespace();
     let row = parts.nth(15).unwrap();
     let row = row[..row.len() - 1].parse::<u64>().unwrap();
@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Dir {
     Left,
@@ -7,7 +5,7 @@ enum Dir {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let instructions = input.trim().split(", ");
 
     let mut x = 0;
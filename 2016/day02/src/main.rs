@@ -1,5 +1,3 @@
-use std::fs;
-
 const PAD1: [[char; 3]; 3] = [['1', '2', '3'], ['4', '5', '6'], ['7', '8', '9']];
 
 const PAD2: [[char; 5]; 5] = [
@@ -11,7 +9,7 @@ const PAD2: [[char; 5]; 5] = [
 ];
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut x1 = 1;
     let mut y1 = 1;
@@ -1,11 +1,9 @@
-use std::fs;
-
 fn is_triangle(a: u64, b: u64, c: u64) -> bool {
     a + b > c && a + c > b && b + c > a
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut total1 = 0;
     let mut total2 = 0;
@@ -1,9 +1,7 @@
-use std::fs;
-
 const NORTHPOLE_OBJECT_STORAGE: &[u8; 24] = b"northpole object storage";
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut counts = [0; 26];
     let mut sum = 0;
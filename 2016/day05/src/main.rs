@@ -1,5 +1,4 @@
 use std::{
-    fs,
     sync::{
         atomic::{AtomicBool, AtomicI64, Ordering},
         mpsc,
@@ -12,7 +11,7 @@ use md5::Digest;
 const BLOCK_SIZE: i64 = 1000;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let input = input.trim();
 
     let index = AtomicI64::new(0);
@@ -1,5 +1,3 @@
-use std::fs;
-
 fn has_abba(s: &str) -> bool {
     if s.len() < 3 {
         return false;
@@ -28,7 +26,7 @@ fn get_abas(s: &str) -> Vec<(u8, u8)> {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut result_part1 = 0;
     let mut result_part2 = 0;
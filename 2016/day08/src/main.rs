@@ -1,7 +1,5 @@
-use std::fs;
-
 #[cfg(feature = "visualize")]
-use screen::Screen;
+use screen::{Options, Screen};
 
 fn rotate_down(grid: &mut [[bool; 50]; 6], x: usize, n: usize) {
     assert!(n < grid.len());
@@ -49,11 +47,15 @@ fn visualize_grid(grid: &[[bool; 50]; 6], screen: &mut Screen) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let instructions = input.lines().collect::<Vec<_>>();
 
     #[cfg(feature = "visualize")]
-    let mut screen = Screen::new(50, 6, 100);
+    let mut screen = Options::from_env()
+        .expect("Invalid visualization options")
+        .map(|options| {
+            Screen::with_options(50, 6, 100, &options).expect("Unable to create screen")
+        });
 
     let mut grid = [[false; 50]; 6];
     for i in instructions {
@@ -63,18 +65,20 @@ fn main() {
             let w = w.parse::<usize>().unwrap();
             let h = h.parse::<usize>().unwrap();
 
-            #[cfg(not(feature = "visualize"))]
-            for row in grid.iter_mut().take(h) {
-                for c in row.iter_mut().take(w) {
-                    *c = true;
+            #[cfg(feature = "visualize")]
+            if let Some(screen) = &mut screen {
+                for y in 0..h {
+                    for x in 0..w {
+                        grid[y][x] = true;
+                        visualize_grid(&grid, screen);
+                    }
                 }
+                continue;
             }
 
-            #[cfg(feature = "visualize")]
-            for y in 0..h {
-                for x in 0..w {
-                    grid[y][x] = true;
-                    visualize_grid(&grid, &mut screen);
+            for row in grid.iter_mut().take(h) {
+                for c in row.iter_mut().take(w) {
+                    *c = true;
                 }
             }
         } else if i.starts_with("rotate row") {
@@ -83,33 +87,40 @@ fn main() {
             let row = row.parse::<usize>().unwrap();
             let len = len.parse::<usize>().unwrap();
 
-            #[cfg(not(feature = "visualize"))]
-            grid[row].rotate_right(len);
-
             #[cfg(feature = "visualize")]
-            for _ in 0..len {
-                grid[row].rotate_right(1);
-                visualize_grid(&grid, &mut screen);
+            if let Some(screen) = &mut screen {
+                for _ in 0..len {
+                    grid[row].rotate_right(1);
+                    visualize_grid(&grid, screen);
+                }
+                continue;
             }
+
+            grid[row].rotate_right(len);
         } else {
             let remainder = &i[16..];
             let (col, len) = remainder.split_once(" by ").unwrap();
             let col = col.parse::<usize>().unwrap();
             let len = len.parse::<usize>().unwrap();
 
-            #[cfg(not(feature = "visualize"))]
-            rotate_down(&mut grid, col, len);
-
             #[cfg(feature = "visualize")]
-            for _ in 0..len {
-                rotate_down(&mut grid, col, 1);
-                visualize_grid(&grid, &mut screen);
+            if let Some(screen) = &mut screen {
+                for _ in 0..len {
+                    rotate_down(&mut grid, col, 1);
+                    visualize_grid(&grid, screen);
+                }
+                continue;
             }
+
+            rotate_down(&mut grid, col, len);
         }
     }
 
+    // the visualization has already shown the final grid
     #[cfg(feature = "visualize")]
-    screen.finish();
+    let visualized = screen.take().map(|mut screen| screen.finish()).is_some();
+    #[cfg(not(feature = "visualize"))]
+    let visualized = false;
 
     let mut result = 0;
     for row in &grid {
@@ -122,13 +133,19 @@ fn main() {
 
     println!("{result}");
 
-    #[cfg(not(feature = "visualize"))]
-    for r in grid {
-        println!(
-            "{}",
-            r.iter()
-                .map(|b| if *b { '█' } else { ' ' })
-                .collect::<String>()
-        );
+    if !visualized {
+        for r in grid {
+            println!(
+                "{}",
+                r.iter()
+                    .map(|b| if *b { '█' } else { ' ' })
+                    .collect::<String>()
+            );
+        }
+    }
+
+    match ocr::recognize(&grid) {
+        Ok(text) => println!("{text}"),
+        Err(e) => eprintln!("Unable to recognize text: {e}"),
     }
 }
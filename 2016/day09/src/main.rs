@@ -1,5 +1,3 @@
-use std::fs;
-
 fn decompress(s: &[u8]) -> (usize, usize) {
     let mut i = 0;
     let mut result1 = 0;
@@ -39,7 +37,7 @@ fn decompress(s: &[u8]) -> (usize, usize) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let (total1, total2) = decompress(input.trim().as_bytes());
     println!("{total1}");
     println!("{total2}");
@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Default, PartialEq, Eq, Clone, Copy)]
 enum Input {
     #[default]
@@ -46,7 +44,7 @@ fn eval(input: Input, bots: &mut [Bot]) -> u64 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut bots: Vec<Bot> = Vec::new();
     let mut outputs: Vec<Input> = Vec::new();
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use rustc_hash::FxHashSet;
 
@@ -111,7 +111,7 @@ fn try_move(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // Parse current state. IMPORTANT OPTIMIZATION: Instead of differentiating
     // between all chemical elements, we only need to count the number of
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 const W: usize = 50;
 
@@ -8,7 +8,7 @@ fn is_space(x: i32, y: i32, num: i32) -> bool {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let num = input.trim().parse::<i32>().unwrap();
 
     let mut seen = vec![false; W * W];
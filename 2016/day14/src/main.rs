@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use rayon::prelude::*;
 
@@ -36,7 +36,7 @@ fn find_fives(s: &[u8]) -> u64 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let salt = input.trim();
 
     let mut keys_found1 = 0;
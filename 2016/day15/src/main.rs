@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Performs the extended Euclidean algorithm. Based on the pseudo-code from
 /// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm but we're only
 /// interested in `y`.
@@ -45,7 +43,7 @@ pub fn chinese_remainder(remainders: &[i64], moduli: &[i64]) -> i64 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let discs = input
         .lines()
         .map(|l| {
@@ -32,7 +32,7 @@
 //!    corresponding range. The binary search is implemented in the
 //!    [Disk::count_ones] method.
 
-use std::{fs, ops::Range};
+use std::ops::Range;
 
 struct Disk {
     /// The length of the initial state in bits
@@ -107,7 +107,7 @@ impl Disk {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let (input, input_len) = input.trim().bytes().fold((0_u64, 0_u64), |prev, b| {
         let mut next = prev.0 << 1;
         if b == b'1' {
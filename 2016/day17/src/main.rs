@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use md5::Context;
 
@@ -7,7 +7,7 @@ fn is_open(b: u8) -> bool {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let passcode = input.trim();
 
     for part1 in [true, false] {
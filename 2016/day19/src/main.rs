@@ -1,7 +1,5 @@
-use std::fs;
-
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let num_elves = input.trim().parse::<usize>().unwrap();
 
     // Part 1: We consider the game to be round-based. In each round, every
@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Instruction {
     SwapPosition(usize, usize),
@@ -12,7 +10,7 @@ enum Instruction {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let instructions = input
         .lines()
         .map(|l| {
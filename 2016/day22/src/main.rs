@@ -1,5 +1,5 @@
 use core::panic;
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use rustc_hash::FxHashSet;
 
@@ -15,7 +15,7 @@ pub struct State {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let nodes = input
         .lines()
         .skip(2)
@@ -136,7 +136,9 @@ fn main() {
     }
 
     #[cfg(feature = "visualize")]
-    visualize::visualize(&grid_with_sizes, &grid, *empty, max_x);
+    if let Some(options) = screen::Options::from_env().expect("Invalid visualization options") {
+        visualize::visualize(&options, &grid_with_sizes, &grid, *empty, max_x);
+    }
 
     // BFS
     let mut queue = VecDeque::new();
@@ -6,11 +6,12 @@ use scarlet::{
     colormap::{ColorMap, ListedColorMap},
     colors::CIELABColor,
 };
-use screen::Screen;
+use screen::{Options, Screen};
 
 use crate::State;
 
 pub fn visualize(
+    options: &Options,
     grid_with_sizes: &[Vec<(usize, usize)>],
     grid: &[Vec<bool>],
     empty: (usize, usize, usize, usize),
@@ -19,7 +20,8 @@ pub fn visualize(
     // initialize screen
     let width = grid_with_sizes[0].len();
     let height = grid_with_sizes.len();
-    let mut screen = Screen::new(width, height, 20);
+    let mut screen =
+        Screen::with_options(width, height, 20, options).expect("Unable to create screen");
 
     // get minimum and maximum disk usage, but exclude empty node as well as the
     // nodes whose data cannot be moved
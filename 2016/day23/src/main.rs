@@ -1,7 +1,4 @@
-use std::{
-    fmt::{Display, Formatter},
-    fs,
-};
+use std::fmt::{Display, Formatter};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Input {
@@ -56,7 +53,7 @@ fn parse_input(s: &str) -> Input {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let instructions = input
         .lines()
         .map(|l| {
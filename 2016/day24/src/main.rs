@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 /// Computes the next permutation of a bitmask where `k` out of `n` bits are
 /// set, in lexicographical order. For example, if `k` is 3 and the current
@@ -72,7 +72,7 @@ fn held_karp(distances: &[i64], n_distances: usize) -> (i64, i64) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
@@ -1,7 +1,5 @@
-use std::fs;
-
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let input = input.trim().parse::<u64>().unwrap();
 
     // part 1 - the numbers in the corners of each ring can be computed as
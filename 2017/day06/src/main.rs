@@ -1,9 +1,9 @@
-use std::{collections::hash_map::Entry, fs};
+use std::collections::hash_map::Entry;
 
 use rustc_hash::{FxBuildHasher, FxHashMap};
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // the problem statement says there are exactly 16 banks, so we can use an
     // array instead of a Vec
@@ -1,4 +1,4 @@
-use std::{collections::hash_map::Entry, fs};
+use std::collections::hash_map::Entry;
 
 use rustc_hash::{FxBuildHasher, FxHashMap};
 
@@ -68,7 +68,7 @@ fn find_weight(name: &str, tree: &FxHashMap<&str, Node>) -> Weight {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // parse input and build tree
     let mut tree: FxHashMap<&str, Node> = FxHashMap::with_capacity_and_hasher(2048, FxBuildHasher);
@@ -1,5 +1,3 @@
-use std::fs;
-
 /// All registers have at most three characters, so we can create a perfect hash
 fn index(reg: &str) -> usize {
     assert!(reg.len() <= 3);
@@ -17,7 +15,7 @@ fn index(reg: &str) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let instructions = input.lines().map(|l| {
         let mut p = l.split_ascii_whitespace();
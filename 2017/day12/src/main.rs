@@ -1,5 +1,3 @@
-use std::fs;
-
 use rustc_hash::{FxBuildHasher, FxHashSet};
 
 struct Node {
@@ -32,7 +30,7 @@ fn union(mut x: usize, mut y: usize, nodes: &mut [Node]) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let (mut nodes, lines): (Vec<Node>, Vec<&str>) = input
         .lines()
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 fn hash(input: &[usize], rowi: usize, row: &mut [bool; 128]) {
     let mut lengths = input.to_vec();
@@ -45,7 +45,7 @@ fn hash(input: &[usize], rowi: usize, row: &mut [bool; 128]) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let input = input.trim();
 
     let mut grid = [[false; 128]; 128];
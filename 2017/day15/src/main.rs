@@ -1,7 +1,7 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut lines = input.lines();
     let mut gen_a = lines
         .next()
@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Multiply `a` with `b` and store the result in `dst`
 fn mul(a: &[usize; 16], b: &[usize; 16], dst: &mut [usize; 16]) {
     for (d, &s) in b.iter().enumerate() {
@@ -41,7 +39,7 @@ fn to_order(moves: &[usize; 16], renames: &[usize; 16]) -> String {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // Perform instructions once but differentiate between moves and renames.
     // This allows us to use binary exponentiation later to get the order of the
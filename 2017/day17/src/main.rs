@@ -1,7 +1,5 @@
-use std::fs;
-
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let steps = input.trim().parse::<usize>().unwrap();
 
     // part 1 - Simulate 2017 rounds and record the position (index) at which
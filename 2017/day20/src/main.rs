@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 struct Point {
     x: i64,
@@ -158,7 +156,7 @@ fn do_collide(particle1: Particle, particle2: Particle) -> bool {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut particles = input
         .lines()
         .map(|l| {
@@ -39,7 +39,6 @@
 //! possible patterns to all their rotated and flipped counter-parts. These
 //! tables are in the `lut` module. The code to generate them can also be found
 //! in this module.
-use std::fs;
 
 use rustc_hash::{FxBuildHasher, FxHashMap};
 
@@ -143,7 +142,7 @@ fn count(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut rules_2x2 = [0; 16];
     let mut rules_3x3 = [0; 512];
 
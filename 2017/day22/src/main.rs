@@ -2,13 +2,12 @@
 //! `visualize` feature enabled to see how the ant starts building a recurrent
 //! "highway" (near the end of the visualization):
 //!
-//!     cargo run --release --features=visualize
+//!     AOC_VISUALIZE=true cargo run --release --features=visualize
 //!
 //! [Langton's Ant]: https://en.wikipedia.org/wiki/Langton%27s_ant
-use std::fs;
 
 #[cfg(feature = "visualize")]
-use screen::WindowedScreen;
+use screen::{Options, WindowedScreen};
 
 const SIZE: usize = 1024;
 
@@ -20,32 +19,50 @@ enum State {
     Flagged,
 }
 
+/// Draw the node at `pos` into the screen's frame buffer
 #[cfg(feature = "visualize")]
-fn visualize(grid: &[State], virus: (usize, usize), screen: &mut WindowedScreen) {
-    let mut map = grid
-        .iter()
-        .map(|s| match s {
-            State::Clean => (' ', (0, 0, 0)),
-            State::Weakened => ('⬮', (237, 167, 57)),
-            State::Infected => ('⬮', (208, 58, 32)),
-            State::Flagged => ('⬮', (112, 216, 215)),
-        })
-        .collect::<Vec<_>>();
-    map[virus.1 * SIZE + virus.0] = ('█', (135, 247, 83));
-    screen.update_with_colors(map, virus);
+fn draw_node(screen: &mut Option<WindowedScreen>, pos: (usize, usize), state: State) {
+    let Some(screen) = screen else {
+        return;
+    };
+    let (c, color) = match state {
+        State::Clean => (' ', (0, 0, 0)),
+        State::Weakened => ('⬮', (237, 167, 57)),
+        State::Infected => ('⬮', (208, 58, 32)),
+        State::Flagged => ('⬮', (112, 216, 215)),
+    };
+    screen.frame_mut().set_with_color(pos.0, pos.1, c, color);
+}
+
+/// Draw the virus at its new position and present the changed nodes
+#[cfg(feature = "visualize")]
+fn visualize(virus: (usize, usize), screen: &mut Option<WindowedScreen>) {
+    let Some(screen) = screen else {
+        return;
+    };
+    screen
+        .frame_mut()
+        .set_with_color(virus.0, virus.1, '█', (135, 247, 83));
+    screen.present(virus);
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let grid = input
         .lines()
         .map(|l| l.bytes().collect::<Vec<_>>())
         .collect::<Vec<_>>();
 
+    #[cfg(feature = "visualize")]
+    let options = Options::from_env().expect("Invalid visualization options");
+
     for part1 in [true, false] {
         #[cfg(feature = "visualize")]
-        let mut screen =
-            WindowedScreen::new(SIZE, SIZE, if part1 { 400 } else { 800 }, Some((2, 2)));
+        let mut screen = options.as_ref().map(|options| {
+            let fps = if part1 { 400 } else { 800 };
+            WindowedScreen::with_options(SIZE, SIZE, fps, Some((2, 2)), options)
+                .expect("Unable to create screen")
+        });
 
         // a map of 1024 * 1024 should be more than large enough (for my input,
         // even 512x512 worked well)
@@ -65,7 +82,14 @@ fn main() {
         );
 
         #[cfg(feature = "visualize")]
-        visualize(&map, virus, &mut screen);
+        if screen.is_some() {
+            for (i, s) in map.iter().enumerate() {
+                if !matches!(s, State::Clean) {
+                    draw_node(&mut screen, (i % SIZE, i / SIZE), *s);
+                }
+            }
+            visualize(virus, &mut screen);
+        }
 
         let mut infects = 0;
         for _frame in 0..(if part1 { 10_000 } else { 10_000_000 }) {
@@ -93,6 +117,9 @@ fn main() {
                     *s = State::Clean;
                 }
             }
+            #[cfg(feature = "visualize")]
+            draw_node(&mut screen, virus, *s);
+
             virus.0 = virus.0.checked_add_signed(dir.0).unwrap();
             virus.1 = virus.1.checked_add_signed(dir.1).unwrap();
 
@@ -101,12 +128,12 @@ fn main() {
             // and 9995000-10000000 of part 2. The visualization would be way
             // too long otherwise.
             if part1 || !(15000..=9995000).contains(&_frame) {
-                visualize(&map, virus, &mut screen);
+                visualize(virus, &mut screen);
             }
         }
 
         #[cfg(feature = "visualize")]
-        {
+        if let Some(mut screen) = screen {
             screen.finish();
             println!();
         }
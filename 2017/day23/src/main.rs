@@ -1,5 +1,3 @@
-use std::fs;
-
 enum Instruction {
     Set(usize, Value),
     Sub(usize, Value),
@@ -108,7 +106,7 @@ fn is_prime(n: i64) -> bool {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let instructions = input
         .lines()
         .map(|l| {
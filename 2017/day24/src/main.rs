@@ -1,5 +1,3 @@
-use std::fs;
-
 fn dfs(port: usize, bridges: &[u64; 64], seen: &mut [u64; 64]) -> (usize, usize, usize) {
     let mut strength = 0;
     let mut maxlen = 0;
@@ -33,7 +31,7 @@ fn dfs(port: usize, bridges: &[u64; 64], seen: &mut [u64; 64]) -> (usize, usize,
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // no port in the input is greater than 64
     let mut bridges: [u64; 64] = [0; 64];
@@ -1,5 +1,3 @@
-use std::fs;
-
 const WORD_LEN: usize = 12;
 
 #[derive(Clone, Copy)]
@@ -68,7 +66,7 @@ fn compress(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let blocks = input.split("\n\n").collect::<Vec<_>>();
 
     let steps = blocks[0]
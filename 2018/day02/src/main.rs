@@ -1,9 +1,7 @@
-use std::fs;
-
 use rustc_hash::FxHashMap;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let words = input
         .lines()
         .map(|l| l.bytes().collect::<Vec<_>>())
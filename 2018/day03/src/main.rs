@@ -1,7 +1,7 @@
-use std::{collections::BTreeMap, fs};
+use std::collections::BTreeMap;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut events = Vec::new();
     let mut rectangles = Vec::new();
     for l in input.lines() {
@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, fs};
+use std::cmp::Ordering;
 
 use rustc_hash::FxHashMap;
 
@@ -35,7 +35,7 @@ impl PartialOrd for Event {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // parse events
     let mut guards: FxHashMap<usize, Vec<i64>> = FxHashMap::default();
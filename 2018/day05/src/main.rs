@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 fn collapse<'a, I>(polymer: I, result: &mut VecDeque<u8>)
 where
@@ -16,8 +16,7 @@ where
 }
 
 fn main() {
-    let polymer = fs::read_to_string("input.txt")
-        .expect("Could not read file")
+    let polymer = input::read_input_or_exit("input.txt")
         .trim()
         .bytes()
         .collect::<Vec<_>>();
@@ -1,4 +1,4 @@
-use std::{fs, sync::Arc};
+use std::sync::Arc;
 
 /// A table of distances between grid cells and input coordinates, calculated on
 /// demand
@@ -324,7 +324,7 @@ fn part1_worker(
 fn main() {
     // This solution is convoluted, but fast ;-)
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut min_x = i64::MAX;
     let mut min_y = i64::MAX;
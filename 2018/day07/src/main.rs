@@ -1,7 +1,6 @@
 use std::{
     cmp::{Ordering, Reverse},
     collections::BinaryHeap,
-    fs,
 };
 
 /// An event that happens when a step has finished executing
@@ -54,7 +53,7 @@ fn schedule(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut graph: [Vec<usize>; 26] = [const { Vec::new() }; 26];
     let mut incoming: [usize; 26] = [0; 26];
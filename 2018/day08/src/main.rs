@@ -1,4 +1,4 @@
-use std::{fs, str::Bytes};
+use std::str::Bytes;
 
 /// This is much faster than using split_ascii_whitespace() and then parse()
 fn parse_number(bytes: &mut Bytes) -> usize {
@@ -39,7 +39,7 @@ fn parse_node(bytes: &mut Bytes, buffer: &mut [usize]) -> (usize, usize) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut buffer = vec![0; input.len()]; // performance: avoid repeated allocation
     let (total1, total2) = parse_node(&mut input.bytes(), &mut buffer);
     println!("{total1}");
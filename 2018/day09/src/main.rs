@@ -1,11 +1,9 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs,
-};
+use std::collections::{HashMap, VecDeque};
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let p = input.split_whitespace().collect::<Vec<_>>();
         let players = p[0].parse::<usize>().unwrap();
         let last_points = p[6].parse::<usize>().unwrap();
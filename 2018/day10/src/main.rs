@@ -1,4 +1,4 @@
-use std::{fs, str::Bytes};
+use std::str::Bytes;
 
 /// This is faster than splitting the lines by whitespace and then using parse()
 fn parse_next_number(bytes: &mut Bytes) -> Option<i64> {
@@ -42,7 +42,7 @@ fn get_bbox(particles: &[(i64, i64, i64, i64)], seconds: i64) -> (i64, i64, i64,
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut bytes = input.bytes();
     let mut particles = Vec::new();
     loop {
@@ -85,9 +85,13 @@ fn main() {
     }
 
     // part 1
-    for r in grid {
+    for r in &grid {
         println!("{}", String::from_iter(r));
     }
+    match ocr::recognize_chars(&grid) {
+        Ok(text) => println!("{text}"),
+        Err(e) => eprintln!("Unable to recognize text: {e}"),
+    }
 
     // part 2
     println!("{seconds}");
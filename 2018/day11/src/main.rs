@@ -1,4 +1,4 @@
-use std::{f32::consts::PI, fs};
+use std::f32::consts::PI;
 
 fn get_sum(prefix_sums: &[i32], x: usize, y: usize, s: usize, w: usize) -> i32 {
     let x = x - 1;
@@ -8,8 +8,7 @@ fn get_sum(prefix_sums: &[i32], x: usize, y: usize, s: usize, w: usize) -> i32 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt")
-        .expect("Could not read file")
+    let input = input::read_input_or_exit("input.txt")
         .trim()
         .parse::<i32>()
         .unwrap();
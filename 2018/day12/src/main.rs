@@ -1,5 +1,3 @@
-use std::fs;
-
 struct Pots {
     /// A bit vector representing the pots. 1 means there is a plant in the pot,
     /// 0 means there isn't.
@@ -100,7 +98,7 @@ impl Pots {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut lines = input.lines();
 
     // parse initial state
@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum Turn {
     Left,
@@ -29,7 +27,7 @@ fn main() {
     // detect a possible crash, we enter a recovery mode where we try the step
     // again and check if the crash actually happens
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
@@ -1,7 +1,6 @@
 use std::{
     cmp::Ordering,
     collections::VecDeque,
-    fs,
     sync::atomic::{self, AtomicBool, AtomicI32},
 };
 
@@ -271,7 +270,7 @@ fn play(grid: &[u8], width: usize, height: usize, attack_elf: i32, part1: bool)
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
     let height = lines.len();
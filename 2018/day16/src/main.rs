@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(PartialEq, Eq, Clone, Copy, Hash)]
 enum Opcode {
     Addr,
@@ -108,7 +106,7 @@ where
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut opcodes = [[true; 16]; 16];
 
@@ -1,4 +1,4 @@
-use std::{fs, str::Bytes};
+use std::str::Bytes;
 
 #[cfg(feature = "visualize")]
 mod visualize;
@@ -106,7 +106,7 @@ fn drop(
 
 fn main() {
     // parse
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut min_x = usize::MAX;
     let mut max_x = usize::MIN;
@@ -172,9 +172,9 @@ fn main() {
     );
 
     #[cfg(feature = "visualize")]
-    {
+    if let Some(options) = screen::Options::from_env().expect("Invalid visualization options") {
         grid[500 - min_x] = b'|';
-        visualize::visualize(&grid, width, height, 500 - min_x);
+        visualize::visualize(&options, &grid, width, height, 500 - min_x);
     }
 
     // part 1
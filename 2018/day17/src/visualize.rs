@@ -1,7 +1,4 @@
-use screen::{
-    WindowedScreen,
-    style::{Color, StyledContent, Stylize, style},
-};
+use screen::{Anchor, Options, PixelMode, WindowedScreen, style::Color};
 
 /// The head of a stream of water
 #[derive(Debug)]
@@ -34,40 +31,27 @@ impl Xorshift {
     }
 }
 
-/// A grid that encodes two vertical blocks in one block using different
-/// foreground and background colors
+/// A grid with one color per tile
 #[derive(Clone)]
-struct HalfGrid {
+struct ColorGrid {
     width: usize,
-    grid: Vec<StyledContent<char>>,
+    pixels: Vec<Color>,
 }
 
-impl HalfGrid {
+impl ColorGrid {
     fn new(width: usize, height: usize) -> Self {
         Self {
             width,
-            grid: vec![style('▀').with(Color::Grey).on(Color::Reset); width * height.div_ceil(2)],
+            pixels: vec![Color::Reset; width * height],
         }
     }
 
     fn set(&mut self, x: usize, y: usize, col: Color) {
-        let yh = y / 2;
-        let s = self.grid[yh * self.width + x].style_mut();
-        if y.is_multiple_of(2) {
-            s.foreground_color = Some(col);
-        } else {
-            s.background_color = Some(col);
-        }
+        self.pixels[y * self.width + x] = col;
     }
 
     fn get(&self, x: usize, y: usize) -> Color {
-        let yh = y / 2;
-        let s = self.grid[yh * self.width + x].style();
-        if y.is_multiple_of(2) {
-            s.foreground_color.unwrap_or(Color::Reset)
-        } else {
-            s.background_color.unwrap_or(Color::Reset)
-        }
+        self.pixels[y * self.width + x]
     }
 }
 
@@ -77,7 +61,7 @@ fn should_fill(x: usize, y: usize, grid: &[u8], wetness: &[f32], width: usize) -
 }
 
 /// Visualize the puzzle
-pub fn visualize(grid: &[u8], width: usize, height: usize, start_x: usize) {
+pub fn visualize(options: &Options, grid: &[u8], width: usize, height: usize, start_x: usize) {
     // grid colors
     let dried = Color::from((104, 104, 91));
     let water_33 = Color::from((197, 177, 134));
@@ -94,15 +78,20 @@ pub fn visualize(grid: &[u8], width: usize, height: usize, start_x: usize) {
     let sand_lighter = Color::from((228, 205, 145));
 
     // create windowed screen
-    let fps = 40;
-    let mut screen = WindowedScreen::new(width, height.div_ceil(2), fps, Some((20, 6)));
+    let fps = options.fps(40);
+    let (cols, rows) = PixelMode::HalfBlock.screen_size(width, height);
+    let mut screen = WindowedScreen::with_options(cols, rows, fps, Some((20, 6)), options)
+        .expect("Unable to create screen");
+
+    // show where the window is in the reservoir
+    screen.set_minimap(Some(Anchor::TopRight));
 
     // create pseudo-random number generator
     let mut rng = Xorshift::new();
 
     // create grid for the screen and a vec storing how "wet" each row is (1.0
     // means 100% wet)
-    let mut new_grid = HalfGrid::new(width, height);
+    let mut new_grid = ColorGrid::new(width, height);
     let mut wetness = vec![0.0; width * height];
 
     // convert original grid to coloured grid
@@ -321,13 +310,23 @@ pub fn visualize(grid: &[u8], width: usize, height: usize, start_x: usize) {
         }
 
         // update screen
-        screen.update_with_style(new_grid.grid.clone(), (camera_x, max_s.y / 2));
+        screen.update_pixels_with_colors(
+            &new_grid.pixels,
+            width,
+            PixelMode::HalfBlock,
+            (camera_x, max_s.y),
+        );
         last_max_x = max_s.x;
         last_max_y = max_s.y;
     }
 
     // perform final update
-    screen.update_with_style(new_grid.grid.clone(), (last_max_x, last_max_y));
+    screen.update_pixels_with_colors(
+        &new_grid.pixels,
+        width,
+        PixelMode::HalfBlock,
+        (last_max_x, last_max_y),
+    );
 
     drop(screen);
 
@@ -1,8 +1,6 @@
-use std::fs;
-
 use rustc_hash::{FxBuildHasher, FxHashMap};
 #[cfg(feature = "visualize")]
-use screen::Screen;
+use screen::{Options, Screen};
 
 const OPEN: u8 = 0b00;
 const TREES: u8 = 0b01;
@@ -27,7 +25,7 @@ fn visualize(grid: &[u8], width: usize, height: usize, screen: &mut Screen) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut width = 0;
     let mut height = 0;
     let mut grid = input
@@ -51,18 +49,20 @@ fn main() {
     // add an empty row at the end to make it easier to count neighbors
     grid.resize(grid.len() + width, OPEN);
 
+    #[cfg(feature = "visualize")]
+    let options = Options::from_env().expect("Invalid visualization options");
+
     for part1 in [true, false] {
         let mut grid = grid.clone();
         let max_steps = if part1 { 10 } else { 1_000_000_000 };
 
         #[cfg(feature = "visualize")]
-        let mut screen = if !part1 {
-            let mut screen = Screen::new(width - 2, height, 20);
+        let mut screen = options.as_ref().filter(|_| !part1).map(|options| {
+            let mut screen = Screen::with_options(width - 2, height, 20, options)
+                .expect("Unable to create screen");
             visualize(&grid, width, height, &mut screen);
-            Some(screen)
-        } else {
-            None
-        };
+            screen
+        });
 
         let mut seen = FxHashMap::with_capacity_and_hasher(1000, FxBuildHasher);
         seen.insert(grid.clone(), 0);
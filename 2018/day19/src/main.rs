@@ -1,5 +1,3 @@
-use std::fs;
-
 #[derive(Clone, Copy)]
 enum Opcode {
     Addr,
@@ -95,7 +93,7 @@ fn main() {
 
     // parse
     let mut pointer_register = 0;
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let program = input
         .lines()
         .filter_map(|l| {
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs, str::Bytes};
+use std::{collections::VecDeque, str::Bytes};
 
 use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
 
@@ -121,7 +121,7 @@ fn dfs_fast(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let input = input[1..].trim().as_bytes();
 
     // Create a map (i.e. an undirected graph of rooms and their neighbors).
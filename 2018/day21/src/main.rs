@@ -1,4 +1,4 @@
-use std::{collections::hash_map::Entry::Vacant, fs};
+use std::collections::hash_map::Entry::Vacant;
 
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -88,7 +88,7 @@ fn main() {
 
     // parse
     let mut pointer_register = 0;
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let program = input
         .lines()
         .filter_map(|l| {
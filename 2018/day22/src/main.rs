@@ -1,7 +1,6 @@
 mod grid;
 
 use crate::grid::Grid;
-use std::fs;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Gear {
@@ -55,7 +54,7 @@ impl Erosion {
 
 fn main() {
     // parse input
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut lines = input.lines();
 
     let depth = lines.next().unwrap()[7..].parse::<u32>().unwrap();
@@ -1,4 +1,4 @@
-use std::{fs, str::Bytes};
+use std::str::Bytes;
 
 use rustc_hash::FxHashMap;
 
@@ -183,7 +183,7 @@ fn parse_next_number(bytes: &mut Bytes) -> Option<i32> {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut bytes = input.bytes();
     let mut bots = Vec::new();
     loop {
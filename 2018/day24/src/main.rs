@@ -1,4 +1,4 @@
-use std::{cell::RefCell, cmp::Reverse, fs, rc::Rc};
+use std::{cell::RefCell, cmp::Reverse, rc::Rc};
 
 #[derive(Clone, PartialEq, Eq)]
 enum Damage {
@@ -201,7 +201,7 @@ fn combat(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let blocks = input.split_once("\n\n").unwrap();
 
     // parse immune system groups
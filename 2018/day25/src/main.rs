@@ -1,4 +1,4 @@
-use std::{fs, str::Bytes};
+use std::str::Bytes;
 
 struct Point {
     x: u16,
@@ -57,7 +57,7 @@ fn main() {
 
     const MAX_DISTANCE: u16 = 3;
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut bytes = input.bytes();
 
     let mut min_x = i32::MAX;
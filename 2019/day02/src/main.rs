@@ -1,5 +1,3 @@
-use std::fs;
-
 fn run(memory: &[usize], noun: usize, verb: usize) -> usize {
     let mut memory = memory.to_owned();
 
@@ -30,7 +28,7 @@ fn run(memory: &[usize], noun: usize, verb: usize) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let memory = input
         .trim()
         .split(',')
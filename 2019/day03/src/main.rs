@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 enum Dir {
     R,
@@ -8,7 +8,7 @@ enum Dir {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut wires = input.lines().map(|l| {
         l.split(',')
             .map(|p| {
@@ -1,5 +1,3 @@
-use std::fs;
-
 fn run(memory: &[i64], input: i64) -> i64 {
     let mut memory = memory.to_owned();
 
@@ -97,7 +95,7 @@ fn run(memory: &[i64], input: i64) -> i64 {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let memory = input
         .trim()
         .split(',')
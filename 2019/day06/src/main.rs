@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 fn get_orbits<'a>(
     n: &'a str,
@@ -18,7 +18,7 @@ fn get_orbits<'a>(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let map = input
         .lines()
         .map(|l| {
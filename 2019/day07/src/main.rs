@@ -1,5 +1,3 @@
-use std::fs;
-
 use itertools::Itertools;
 
 struct Machine {
@@ -118,8 +116,9 @@ impl Machine {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let memory = input
             .trim()
             .split(',')
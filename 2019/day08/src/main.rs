@@ -1,7 +1,5 @@
-use std::fs;
-
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let pixels = input.trim().chars().collect::<Vec<_>>();
 
     let w = 25;
@@ -55,4 +53,8 @@ fn main() {
     image
         .iter()
         .for_each(|r| println!("{}", r.iter().collect::<String>()));
+    match ocr::recognize_chars(&image) {
+        Ok(text) => println!("{text}"),
+        Err(e) => eprintln!("Unable to recognize text: {e}"),
+    }
 }
@@ -1,5 +1,3 @@
-use std::fs;
-
 struct Machine {
     memory: Vec<i64>,
     input1: i64,
@@ -143,7 +141,7 @@ impl Machine {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let memory = input
         .trim()
         .split(',')
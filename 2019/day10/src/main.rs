@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, fs};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct Asteroid {
@@ -48,7 +48,7 @@ fn in_sight(asteroids: &[Asteroid], from: Asteroid) -> BTreeMap<Angle, Asteroid>
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut asteroids = input
         .lines()
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 struct Machine {
     memory: Vec<i64>,
@@ -143,8 +143,9 @@ impl Machine {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let memory = input
             .trim()
             .split(',')
@@ -207,6 +208,10 @@ fn main() {
             display
                 .iter()
                 .for_each(|r| println!("{}", String::from_iter(r)));
+            match ocr::recognize_chars(&display) {
+                Ok(text) => println!("{text}"),
+                Err(e) => eprintln!("Unable to recognize text: {e}"),
+            }
         }
     }
 }
@@ -1,5 +1,3 @@
-use std::fs;
-
 use num::integer::lcm;
 
 fn calculate_energy(moons: &[[i32; 3]], steps: usize) -> u64 {
@@ -72,7 +70,7 @@ fn find_cycle(mut moons: Vec<i32>) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let moons = input
         .lines()
         .map(|l| {
@@ -1,7 +1,6 @@
-use crossterm::{cursor, style, terminal, ExecutableCommand};
+#[cfg(feature = "visualize")]
+use screen::{Options, Screen, ScreenError};
 use std::error::Error;
-use std::io::stdout;
-use std::{env, fs, thread, time};
 
 struct Machine {
     memory: Vec<i64>,
@@ -145,8 +144,87 @@ impl Machine {
     }
 }
 
+/// Draws the game on the terminal
+#[cfg(feature = "visualize")]
+struct Visualization {
+    options: Options,
+
+    /// The tiles drawn by the game so far and the screen, which is created as
+    /// soon as the game has drawn the whole board and displays the score for
+    /// the first time
+    tiles: Vec<Vec<char>>,
+    width: usize,
+    screen: Option<Screen>,
+}
+
+#[cfg(feature = "visualize")]
+impl Visualization {
+    fn new(options: Options) -> Self {
+        Self {
+            options: Options {
+                header_lines: 1,
+                ..options
+            },
+            tiles: Vec::new(),
+            width: 0,
+            screen: None,
+        }
+    }
+
+    /// Create the screen if the board is complete
+    fn start(&mut self) -> Result<(), ScreenError> {
+        if self.screen.is_none() {
+            self.width = self.tiles.iter().map(|row| row.len()).max().unwrap_or(0);
+            self.screen = Some(Screen::with_options(
+                self.width,
+                self.tiles.len(),
+                500,
+                &self.options,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Draw a tile of the given type. Draw a new frame whenever the ball has
+    /// moved.
+    fn set_tile(&mut self, x: usize, y: usize, tpe: i64, score: i64) {
+        let c = match tpe {
+            0 => ' ',
+            1 => '█',
+            2 => '▪',
+            3 => '—',
+            4 => '○',
+            _ => panic!(),
+        };
+        if self.tiles.len() <= y {
+            self.tiles.resize(y + 1, Vec::new());
+        }
+        if self.tiles[y].len() <= x {
+            self.tiles[y].resize(x + 1, ' ');
+        }
+        self.tiles[y][x] = c;
+
+        if tpe == 4 {
+            if let Some(screen) = &mut self.screen {
+                let grid = self
+                    .tiles
+                    .iter()
+                    .flat_map(|row| {
+                        row.iter()
+                            .copied()
+                            .chain(std::iter::repeat(' '))
+                            .take(self.width)
+                    })
+                    .collect();
+                screen.set_header(0, format!("Score: {score}"));
+                screen.update(grid);
+            }
+        }
+    }
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut memory = input
         .trim()
         .split(',')
@@ -156,22 +234,9 @@ fn main() -> Result<(), Box<dyn Error>> {
     // play for free
     memory[0] = 2;
 
-    // should the game be visualized on the terminal?
-    let visualize = env::var("AOC_VISUALIZE").is_ok();
-
-    let mut stdout = stdout();
-    let pos = if visualize {
-        // make space on screen and reset cursor
-        stdout.execute(terminal::ScrollUp(25))?;
-        stdout.execute(cursor::MoveTo(0, cursor::position()?.1 - 25))?;
-
-        // hide cursor
-        stdout.execute(cursor::Hide)?;
-
-        cursor::position()?
-    } else {
-        (0u16, 0u16)
-    };
+    // the game is only visualized if it is enabled at runtime
+    #[cfg(feature = "visualize")]
+    let mut visualization = Options::from_env()?.map(Visualization::new);
 
     let mut robot = Machine::new(&memory, 0);
     let mut block_tiles = 0;
@@ -193,27 +258,22 @@ fn main() -> Result<(), Box<dyn Error>> {
                 if let Some(tpe) = robot.run(joystick) {
                     if x == -1 && y == 0 {
                         score = tpe;
+                        #[cfg(feature = "visualize")]
+                        if let Some(visualization) = &mut visualization {
+                            visualization.start()?;
+                        }
                     } else {
-                        let c = match tpe {
-                            0 => ' ',
-                            1 => '█',
-                            2 => {
-                                block_tiles += 1;
-                                '▪'
-                            }
-                            3 => {
-                                paddle_x = x;
-                                '—'
-                            }
-                            4 => {
-                                ball_x = x;
-                                '○'
-                            }
+                        match tpe {
+                            0 | 1 => {}
+                            2 => block_tiles += 1,
+                            3 => paddle_x = x,
+                            4 => ball_x = x,
                             _ => panic!(),
-                        };
-                        if visualize {
-                            stdout.execute(cursor::MoveTo(pos.0 + x as u16, pos.1 + y as u16))?;
-                            stdout.execute(style::Print(c))?;
+                        }
+
+                        #[cfg(feature = "visualize")]
+                        if let Some(visualization) = &mut visualization {
+                            visualization.set_tile(x as usize, y as usize, tpe, score);
                         }
                     }
                 } else {
@@ -225,15 +285,11 @@ fn main() -> Result<(), Box<dyn Error>> {
         } else {
             break;
         }
-
-        if visualize {
-            thread::sleep(time::Duration::from_millis(1));
-        }
     }
 
-    if visualize {
-        stdout.execute(cursor::MoveTo(0, 25))?;
-        stdout.execute(cursor::Show)?;
+    #[cfg(feature = "visualize")]
+    if let Some(mut screen) = visualization.and_then(|v| v.screen) {
+        screen.finish();
     }
 
     // part 1
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 type Dep<'a> = (i64, &'a str);
 
@@ -41,7 +41,7 @@ fn produce<'a>(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let reactions = input
         .lines()
         .map(|l| {
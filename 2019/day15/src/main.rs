@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    fs,
-};
+use std::collections::{HashSet, VecDeque};
 
 #[derive(Clone)]
 struct Machine {
@@ -154,7 +151,7 @@ struct State {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let memory = input
         .trim()
         .split(',')
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 use compress::compress;
 use instruction::{Instruction, Turn};
@@ -166,7 +166,7 @@ fn find_turn(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let memory = input
         .trim()
         .split(',')
@@ -1,5 +1,3 @@
-use std::fs;
-
 const LOSE: u64 = 0;
 const DRAW: u64 = 3;
 const WIN: u64 = 6;
@@ -9,7 +7,7 @@ const PAPER: u64 = 2;
 const SCISSORS: u64 = 3;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     // part 1
@@ -1,5 +1,3 @@
-use std::fs;
-
 static PATTERNS: &[&str] = &[
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
@@ -22,8 +20,9 @@ fn search(l: &str, r: impl Iterator<Item = usize>, part1: bool) -> u32 {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
         let mut sum = 0;
         for l in lines {
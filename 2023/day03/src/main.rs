@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 fn line_has_part(
     line: &str,
@@ -41,8 +41,9 @@ fn has_part(
 fn main() {
     let r = regex::Regex::new(r"\d+").unwrap();
 
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
 
         let mut gears: HashMap<(usize, usize), Vec<u32>> = HashMap::new();
@@ -1,7 +1,5 @@
-use std::fs;
-
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut lines = input.lines().map(|l| (1, l)).collect::<Vec<_>>();
 
     let mut total_score = 0;
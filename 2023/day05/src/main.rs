@@ -1,4 +1,4 @@
-use std::{fs, ops::Range};
+use std::ops::Range;
 
 fn parse_mapping(b: &str) -> Vec<(Range<usize>, Range<usize>)> {
     b.trim()
@@ -15,8 +15,9 @@ fn parse_mapping(b: &str) -> Vec<(Range<usize>, Range<usize>)> {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let mut blocks = input.split("\n\n");
 
         let mut ranges = Vec::new();
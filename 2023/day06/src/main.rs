@@ -1,5 +1,3 @@
-use std::fs;
-
 fn parse_line(line: &str, part1: bool) -> Vec<u64> {
     if part1 {
         line.split_whitespace()
@@ -13,8 +11,9 @@ fn parse_line(line: &str, part1: bool) -> Vec<u64> {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let (times, records) = input.trim().split_once("\n").unwrap();
 
         let times: Vec<u64> = parse_line(times, part1);
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 fn count(a: &str) -> Vec<(char, u64)> {
     let mut result = HashMap::new();
@@ -43,8 +43,9 @@ fn rank(a: &str, part1: bool) -> usize {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let mut lines = input
             .lines()
             .map(|l| {
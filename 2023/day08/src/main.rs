@@ -1,11 +1,8 @@
 use num::integer::lcm;
-use std::{
-    collections::{hash_map::Entry::Vacant, HashMap},
-    fs,
-};
+use std::collections::{hash_map::Entry::Vacant, HashMap};
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let (instructions, lines) = input.split_once("\n\n").unwrap();
     let instructions = instructions.chars().collect::<Vec<_>>();
     let mut map = HashMap::new();
@@ -1,5 +1,3 @@
-use std::fs;
-
 fn extrapolate(n: &[i64], part1: bool) -> i64 {
     let mut children = Vec::new();
     for i in 0..n.len() - 1 {
@@ -18,8 +16,9 @@ fn extrapolate(n: &[i64], part1: bool) -> i64 {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
         let mut sum = 0;
         for l in lines {
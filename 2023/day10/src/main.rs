@@ -1,5 +1,3 @@
-use std::fs;
-
 fn find_start(grid: &[Vec<char>]) -> Option<(usize, usize)> {
     for (y, row) in grid.iter().enumerate() {
         for (x, col) in row.iter().enumerate() {
@@ -70,7 +68,7 @@ fn find_loop(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let grid = input
         .lines()
         .map(|l| l.chars().collect::<Vec<_>>())
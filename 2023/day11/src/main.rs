@@ -1,10 +1,9 @@
-use std::fs;
-
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
         let additional_steps_per_empty = if part1 { 2 } else { 1000000 };
 
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let grid = input
             .lines()
             .map(|l| l.chars().collect::<Vec<_>>())
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 fn compare(s: &[char], cs: &[usize], cache: &mut HashMap<(usize, usize), usize>) -> usize {
     if let Some(prev) = cache.get(&(s.len(), cs.len())) {
@@ -47,8 +47,9 @@ fn compare(s: &[char], cs: &[usize], cache: &mut HashMap<(usize, usize), usize>)
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines: Vec<(&str, Vec<usize>)> = input
             .lines()
             .map(|l| {
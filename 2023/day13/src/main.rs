@@ -1,5 +1,3 @@
-use std::fs;
-
 fn transpose(pattern: &[Vec<char>]) -> Vec<Vec<char>> {
     let mut new_pattern = vec![vec![' '; pattern.len()]; pattern[0].len()];
     for (y, row) in pattern.iter().enumerate() {
@@ -34,8 +32,9 @@ fn find(p: &[Vec<char>], part1: bool) -> Option<usize> {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let patterns: Vec<Vec<Vec<char>>> = input
             .split("\n\n")
             .map(|b| b.lines().map(|l| l.chars().collect()).collect())
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 fn rotate(grid: &[Vec<char>]) -> Vec<Vec<char>> {
     let mut new_grid = vec![vec![' '; grid.len()]; grid[0].len()];
@@ -38,7 +38,7 @@ fn calculate_load(grid: &[Vec<char>]) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut grid = input
         .lines()
         .map(|l| l.chars().collect::<Vec<_>>())
@@ -1,5 +1,3 @@
-use std::fs;
-
 fn hash(s: &str) -> usize {
     let mut result = 0;
     for c in s.as_bytes() {
@@ -11,7 +9,7 @@ fn hash(s: &str) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.trim().split(",").collect::<Vec<_>>();
 
     let mut part1_total = 0;
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 fn diri(b: (i32, i32, i32, i32)) -> usize {
     if b.2 == 1 {
@@ -72,7 +72,7 @@ fn energize(start: (i32, i32, i32, i32), grid: &[u8], w: usize, h: usize) -> usi
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let grid = lines
         .iter()
@@ -1,7 +1,4 @@
-use std::{
-    collections::{BinaryHeap, HashSet},
-    fs,
-};
+use std::collections::{BinaryHeap, HashSet};
 
 #[derive(Eq, PartialEq, Hash)]
 struct Node {
@@ -26,11 +23,12 @@ impl Ord for Node {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
         let min_steps = if part1 { 1 } else { 4 };
         let max_steps = if part1 { 3 } else { 10 };
 
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let grid = input
             .lines()
             .map(|l| {
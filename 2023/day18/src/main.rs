@@ -1,8 +1,7 @@
-use std::fs;
-
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
 
         let mut pos = (0i64, 0i64);
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs, ops::RangeInclusive};
+use std::{collections::HashMap, ops::RangeInclusive};
 
 enum Command<'a> {
     Accept,
@@ -106,7 +106,7 @@ fn apply(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let (workflows, parts) = input.split_once("\n\n").unwrap();
 
     let mut rules = HashMap::new();
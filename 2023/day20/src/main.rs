@@ -1,7 +1,6 @@
 use std::{
     cell::RefCell,
     collections::{HashMap, VecDeque},
-    fs,
 };
 
 #[derive(Debug)]
@@ -20,7 +19,7 @@ enum Mod<'a> {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     // get all modules and their destinations
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs, hash::Hash};
+use std::{collections::HashMap, hash::Hash};
 
 use bitarray::BitArray;
 
@@ -136,8 +136,9 @@ fn main() {
     // solutions you find on the Internet, but it should work for every input
     // and every step count
 
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let grid = input
             .lines()
             .map(|l| l.chars().collect::<Vec<_>>())
@@ -1,4 +1,4 @@
-use std::{fs, vec};
+use std::vec;
 
 fn fall(grid: &mut [i32], w: usize, brick: ((i32, i32, i32), (i32, i32, i32))) -> i32 {
     let ((x1, y1, z1), (x2, y2, z2)) = brick;
@@ -19,7 +19,7 @@ fn fall(grid: &mut [i32], w: usize, brick: ((i32, i32, i32), (i32, i32, i32))) -
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let mut bricks = input
         .lines()
         .map(|l| {
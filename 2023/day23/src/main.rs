@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs,
-};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 #[derive(Debug)]
 struct State {
@@ -62,8 +59,9 @@ fn insert_edge(
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
         let grid = lines
             .iter()
@@ -178,8 +178,6 @@
 //! Finally, we perform Newton-Raphson using some random (but reasonable) start
 //! values for `pr`, `vr`, `t1`, `t2`, `t3` (see main function).
 
-use std::fs;
-
 use crate::gauss::solve;
 
 mod gauss;
@@ -356,7 +354,7 @@ fn newton(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let mut points = Vec::new();
@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs,
-};
+use std::collections::{HashMap, VecDeque};
 
 /// Brandes' algorithm calculates the betweenness centrality of graph nodes.
 /// It basically finds the shortest path between each pair of nodes and counts
@@ -81,7 +78,7 @@ fn count_cluster(edges: &[Vec<usize>], i: usize, seen: &mut [bool]) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     // parse input and create bi-directional graph
@@ -1,7 +1,7 @@
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let mut left = Vec::new();
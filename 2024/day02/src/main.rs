@@ -1,5 +1,3 @@
-use std::fs;
-
 fn is_safe(numbers: &[i64]) -> bool {
     let mut diffs = Vec::new();
     for i in 1..numbers.len() {
@@ -9,8 +7,9 @@ fn is_safe(numbers: &[i64]) -> bool {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
 
         let mut total = 0;
@@ -1,5 +1,3 @@
-use std::fs;
-
 const WORD: [char; 4] = ['X', 'M', 'A', 'S'];
 const DIRS: [(i32, i32); 8] = [
     (0, 1),
@@ -13,7 +11,7 @@ const DIRS: [(i32, i32); 8] = [
 ];
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let grid = input
         .lines()
         .map(|l| l.chars().collect::<Vec<_>>())
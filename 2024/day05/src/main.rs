@@ -1,7 +1,7 @@
-use std::{cmp::Ordering, collections::HashSet, fs};
+use std::{cmp::Ordering, collections::HashSet};
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     let mut edges: HashSet<(u32, u32)> = HashSet::new();
@@ -1,5 +1,3 @@
-use std::fs;
-
 const DIRS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
 
 struct Grid {
@@ -61,7 +59,7 @@ fn find_loop(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
     let height = lines.len();
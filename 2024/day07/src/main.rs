@@ -1,5 +1,3 @@
-use std::fs;
-
 fn check(cur: u64, numbers: &[u64], i: usize, part1: bool) -> bool {
     if i == 0 {
         return cur == numbers[0];
@@ -28,8 +26,9 @@ fn check(cur: u64, numbers: &[u64], i: usize, part1: bool) -> bool {
 }
 
 fn main() {
+    let input = input::read_input_or_exit("input.txt");
+
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let lines = input.lines().collect::<Vec<_>>();
 
         let mut total = 0;
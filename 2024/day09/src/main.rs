@@ -1,4 +1,4 @@
-use std::{cmp::Reverse, collections::BinaryHeap, fs};
+use std::{cmp::Reverse, collections::BinaryHeap};
 
 #[derive(Debug, Copy, Clone)]
 struct Item {
@@ -83,7 +83,7 @@ fn part2(disk: Vec<Item>, mut space_index: [BinaryHeap<Reverse<usize>>; 10]) ->
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut disk = Vec::new();
     let mut space_index = [const { BinaryHeap::new() }; 10];
@@ -1,8 +1,7 @@
 use std::collections::VecDeque;
-use std::fs;
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
     let height = lines.len();
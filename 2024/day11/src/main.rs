@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::fs;
 
 fn blink(
     n: u64,
@@ -43,7 +42,7 @@ fn blink(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let mut cache = HashMap::with_capacity(5000);
 
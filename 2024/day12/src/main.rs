@@ -1,5 +1,4 @@
 use std::collections::VecDeque;
-use std::fs;
 
 struct Region {
     area: usize,
@@ -67,7 +66,7 @@ fn remove_connected(s: (i32, i32), sides: &mut Vec<(i32, i32)>) {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
     let height = lines.len();
@@ -26,12 +26,11 @@
 //!     
 //!     mb = (pzx * bay - pzy * bax) / (bay * bbx - bax * bby)
 //!
-use std::fs;
 
 fn main() {
-    for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
+    for part1 in [true, false] {
         let mut total = 0;
         let claws = input.split("\n\n").collect::<Vec<_>>();
         for claw in claws {
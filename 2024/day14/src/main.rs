@@ -2,8 +2,6 @@
 //! problem differently, but that's how I did it during the contest and I would
 //! like to keep the original code for posterity. :-)
 
-use std::fs;
-
 #[allow(unused)]
 fn print_grid(grid: &[usize], w: usize, h: usize) {
     for y in 0..h {
@@ -29,7 +27,7 @@ fn count(grid: &[usize], w: usize, sx: usize, ex: usize, sy: usize, ey: usize) -
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let w = 101;
@@ -1,7 +1,5 @@
-use screen::Screen;
-use std::{env, fs};
-
-mod screen;
+#[cfg(feature = "visualize")]
+use screen::{Options, Screen};
 
 fn is_movable_vertical(grid: &[u8], w: usize, b: (usize, usize), y: usize, dy: isize) -> bool {
     let ny = y.checked_add_signed(dy).unwrap();
@@ -87,15 +85,18 @@ fn move_horizontal(grid: &mut [u8], w: usize, pos: (usize, usize), dx: isize) {
     }
 }
 
+/// Move the robot and return the sum of the boxes' GPS coordinates. Call
+/// `on_move` after each instruction with the grid, the robot's position, and
+/// the number of instructions executed so far.
 fn run_instructions(
     mut pos: (usize, usize),
     instructions: Vec<u8>,
     mut grid: Vec<u8>,
     w: usize,
     h: usize,
-    screen: &mut Option<Screen>,
+    mut on_move: impl FnMut(&[u8], (usize, usize), usize),
 ) -> usize {
-    for instr in instructions {
+    for (i, instr) in instructions.into_iter().enumerate() {
         match instr {
             // move right
             b'>' => {
@@ -139,11 +140,7 @@ fn run_instructions(
             _ => panic!("Unknown instruction: {}", instr),
         }
 
-        if let Some(ref mut screen) = screen {
-            grid[pos.1 * w + pos.0] = b'@';
-            screen.update(&grid);
-            grid[pos.1 * w + pos.0] = b'.';
-        }
+        on_move(&grid, pos, i + 1);
     }
 
     let mut total = 0;
@@ -159,12 +156,41 @@ fn run_instructions(
     total
 }
 
+/// Draw the grid with the robot after `i` of `n` instructions
+#[cfg(feature = "visualize")]
+fn visualize(
+    screen: &mut Option<Screen>,
+    grid: &[u8],
+    w: usize,
+    pos: (usize, usize),
+    i: usize,
+    n: usize,
+) {
+    let Some(screen) = screen else {
+        return;
+    };
+    let new_grid = grid
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| match c {
+            _ if j == pos.1 * w + pos.0 => '@',
+            b'.' => ' ',
+            b'#' => '█',
+            c => c as char,
+        })
+        .collect();
+    screen.set_footer(0, format!("Move {i}/{n}"));
+    screen.update(new_grid);
+}
+
 fn main() {
     // should the grid be visualized on the terminal?
-    let visualize = env::var("AOC_VISUALIZE").is_ok();
+    #[cfg(feature = "visualize")]
+    let options = Options::from_env().expect("Invalid visualization options");
+
+    let input = input::read_input_or_exit("input.txt");
 
     for part1 in [true, false] {
-        let input = fs::read_to_string("input.txt").expect("Could not read file");
         let (grid, instructions) = input.split_once("\n\n").unwrap();
 
         let grid_lines = grid.lines().collect::<Vec<_>>();
@@ -208,11 +234,14 @@ fn main() {
             grid = wider_grid;
         }
 
-        let mut screen = if visualize {
-            Some(Screen::new(width, height))
-        } else {
-            None
-        };
+        #[cfg(feature = "visualize")]
+        let mut screen = options.as_ref().map(|options| {
+            let options = Options {
+                footer_lines: 1,
+                ..options.clone()
+            };
+            Screen::with_options(width, height, 200, &options).expect("Unable to create screen")
+        });
 
         // find robot
         let mut pos = (0, 0);
@@ -226,8 +255,16 @@ fn main() {
         }
         grid[pos.1 * width + pos.0] = b'.';
 
-        let total = run_instructions(pos, instructions, grid, width, height, &mut screen);
+        #[cfg(feature = "visualize")]
+        let n = instructions.len();
+        #[cfg(feature = "visualize")]
+        let on_move = |grid: &[u8], pos, i| visualize(&mut screen, grid, width, pos, i, n);
+        #[cfg(not(feature = "visualize"))]
+        let on_move = |_: &[u8], _, _| {};
+
+        let total = run_instructions(pos, instructions, grid, width, height, on_move);
 
+        #[cfg(feature = "visualize")]
         if let Some(mut screen) = screen {
             screen.finish();
         }
@@ -1,5 +1,3 @@
-use std::fs;
-
 fn run(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64> {
     let mut ip = 0;
     let mut output = Vec::new();
@@ -40,7 +38,7 @@ fn run(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64> {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let a = lines[0][12..].parse::<u64>().unwrap();
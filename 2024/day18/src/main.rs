@@ -1,5 +1,4 @@
 use std::collections::VecDeque;
-use std::fs;
 
 fn bfs(grid: &mut [bool], max_bytes: usize, bytes: &[(i32, i32)]) -> Option<usize> {
     grid.fill(false);
@@ -32,7 +31,7 @@ fn bfs(grid: &mut [bool], max_bytes: usize, bytes: &[(i32, i32)]) -> Option<usiz
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let bytes = input
         .lines()
         .map(|l| {
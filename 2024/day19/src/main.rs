@@ -1,5 +1,3 @@
-use std::fs;
-
 use trie::Trie;
 
 mod trie;
@@ -24,7 +22,7 @@ fn dfs(design: &[u8], patterns: &Trie, cache: &mut [usize]) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let patterns = lines[0]
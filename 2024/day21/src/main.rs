@@ -1,5 +1,4 @@
 use std::collections::{HashMap, VecDeque};
-use std::fs;
 use std::rc::Rc;
 
 pub const DIRS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
@@ -143,7 +142,7 @@ fn find_shortest_sequence(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let mut cache = HashMap::new();
@@ -6,7 +6,6 @@
 //! first visit a node that does not belong to the clique.
 
 use std::collections::VecDeque;
-use std::fs;
 
 // all node names consist of exactly two lower-case chars between 'a' and 'z'
 fn encode(node: &str) -> usize {
@@ -23,7 +22,7 @@ fn decode(node: usize) -> String {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
 
     let mut neighbors = [const { Vec::new() }; 26 * 26];
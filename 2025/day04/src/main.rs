@@ -1,8 +1,7 @@
-use std::{collections::VecDeque, env, fs};
+use std::collections::VecDeque;
 
-use crate::screen::Screen;
-
-mod screen;
+#[cfg(feature = "visualize")]
+use screen::{Options, PixelMode, Screen};
 
 pub const CLOCKWISE: [(i64, i64); 8] = [
     (1, 0),   // →
@@ -20,12 +19,15 @@ trait StackOrQueue {
     fn pop(&mut self) -> Option<(usize, usize)>;
 }
 
+/// A stack is only used to visualize removing the rolls in a different order
+#[cfg(feature = "visualize")]
 #[derive(Default)]
 struct Stack(Vec<(usize, usize)>);
 
 #[derive(Default)]
 struct Queue(VecDeque<(usize, usize)>);
 
+#[cfg(feature = "visualize")]
 impl StackOrQueue for Stack {
     fn push(&mut self, xy: (usize, usize)) {
         self.0.push(xy);
@@ -46,8 +48,11 @@ impl StackOrQueue for Queue {
     }
 }
 
-fn run<T: StackOrQueue>(visualize: bool, mut queue: T) {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+/// Solve the puzzle by removing rolls of paper in the order given by `queue`.
+/// Call `on_remove` with the counts of all rolls (0 if removed) and the width
+/// of the grid after each roll has been removed.
+fn run<T: StackOrQueue>(mut queue: T, mut on_remove: impl FnMut(&[u8], usize)) {
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
     let height = lines.len();
@@ -88,21 +93,13 @@ fn run<T: StackOrQueue>(visualize: bool, mut queue: T) {
     }
     println!("{total1}");
 
-    let mut screen = if visualize {
-        Some(Screen::new(width, height))
-    } else {
-        None
-    };
-
     // Part 2: For each of the rolls in the queue, remove them and decrease the
     // count of all their neighbors. If the count of a neighbor falls below the
     // limit, add it to the queue too.
     let mut total2 = 0;
     while let Some((x, y)) = queue.pop() {
-        if let Some(screen) = &mut screen {
-            counts[y * width + x] = 0;
-            screen.update(&counts);
-        }
+        counts[y * width + x] = 0;
+        on_remove(&counts, width);
 
         total2 += 1;
         for (dx, dy) in CLOCKWISE {
@@ -125,19 +122,36 @@ fn run<T: StackOrQueue>(visualize: bool, mut queue: T) {
         }
     }
 
-    if let Some(mut screen) = screen {
-        screen.finish();
-    }
+    // let a visualization restore the terminal before printing the result
+    drop(on_remove);
 
     println!("{total2}");
 }
 
+/// Create a callback for [run] that draws the remaining rolls of paper. The
+/// screen is created when the first roll is removed.
+#[cfg(feature = "visualize")]
+fn visualizer(options: &Options) -> impl FnMut(&[u8], usize) {
+    let mut screen: Option<Screen> = None;
+    move |counts, width| {
+        let screen = screen.get_or_insert_with(|| {
+            let height = counts.len() / width;
+            let (cols, rows) = PixelMode::HalfBlock.screen_size(width, height);
+            Screen::with_options(cols, rows, 500, options).expect("Unable to create screen")
+        });
+        let pixels = counts.iter().map(|&c| c > 0).collect::<Vec<_>>();
+        screen.update_pixels(&pixels, width, PixelMode::HalfBlock);
+    }
+}
+
 fn main() {
-    let visualize = env::var("AOC_VISUALIZE").is_ok();
-    if visualize {
-        run(true, Stack::default());
-        run(true, Queue::default());
-    } else {
-        run(false, Queue::default());
+    // visualize removing the rolls in two different orders
+    #[cfg(feature = "visualize")]
+    if let Some(options) = Options::from_env().expect("Invalid visualization options") {
+        run(Stack::default(), visualizer(&options));
+        run(Queue::default(), visualizer(&options));
+        return;
     }
+
+    run(Queue::default(), |_, _| {});
 }
@@ -1,8 +1,6 @@
-use std::fs;
-
 fn main() {
     // parse input
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let (block1, block2) = input.split_once("\n\n").unwrap();
 
     let mut ranges = block1
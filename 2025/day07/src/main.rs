@@ -1,5 +1,3 @@
-use std::fs;
-
 fn dfs(
     x: usize,
     y: usize,
@@ -33,7 +31,7 @@ fn dfs(
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let lines = input.lines().collect::<Vec<_>>();
     let width = lines[0].len();
     let height = lines.len();
@@ -1,5 +1,3 @@
-use std::fs;
-
 struct Node {
     parent: usize,
     size: usize,
@@ -32,7 +30,7 @@ fn union(x: usize, y: usize, nodes: &mut [Node]) -> usize {
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
     let boxes = input
         .lines()
         .map(|l| {
@@ -6,10 +6,7 @@
 //! * https://www.reddit.com/r/adventofcode/comments/1pit2un/2029_day_9_part_2_i_solved_this_one_but_my_code/
 //! * https://www.reddit.com/r/adventofcode/comments/1piqgc2/2025_day_9_check_your_code_with_this_test_input/
 
-use std::{
-    collections::{HashMap, VecDeque},
-    fs,
-};
+use std::collections::{HashMap, VecDeque};
 
 // Right, Down, Left, Up
 const DIRS: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
@@ -84,7 +81,7 @@ fn fill(pos: (usize, usize), grid: &mut [Vec<u64>], width: usize, height: usize)
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // parse coordinates
     let mut coords = Vec::new();
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::fs;
 
 use rayon::prelude::*;
 
@@ -253,7 +252,7 @@ fn dfs_part2(joltage: Vec<usize>, available_buttons_mask: u32, buttons: &[Vec<us
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     // parse input
     let machines = input
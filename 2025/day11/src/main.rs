@@ -1,5 +1,3 @@
-use std::fs;
-
 /// Nodes always have three characters and all of them are lowercase letters
 /// between 'a' and 'z', so we can compute a perfect hash
 fn index(node: &str) -> usize {
@@ -30,7 +28,7 @@ fn count(pos: usize, end: usize, edges: &Vec<Vec<usize>>, cache: &mut Vec<u64>)
 }
 
 fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let you = index("you");
     let out = index("out");
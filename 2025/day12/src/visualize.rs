@@ -1,9 +1,9 @@
 use dialoguer::Select;
 use dialoguer::theme::ColorfulTheme;
 
-use crate::screen::Screen;
+use screen::style::{Stylize, style};
+use screen::{Colormap, Options, Palette, Screen};
 use std::collections::HashSet;
-use std::fs;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Visualization {
@@ -11,6 +11,43 @@ enum Visualization {
     Simple,
 }
 
+/// Draws an area with one color per present
+struct AreaScreen {
+    screen: Screen,
+    palette: Palette,
+}
+
+impl AreaScreen {
+    fn new(width: usize, height: usize, options: &Options) -> Self {
+        let mut screen =
+            Screen::with_options(width, height, 500, options).expect("Unable to create screen");
+
+        // draw every step of the search
+        screen.set_synchronous(true);
+
+        // skip the dark blue at the beginning of the colormap and shuffle the
+        // colors, so adjacent presents can be told apart
+        let palette = Palette::from_colormap(Colormap::Turbo, 0.1..=1.0, 200).shuffled(12);
+
+        Self { screen, palette }
+    }
+
+    fn update(&mut self, area: &[Vec<u64>]) {
+        let grid = area
+            .iter()
+            .flatten()
+            .map(|&i| {
+                if i == 0 {
+                    style('.')
+                } else {
+                    '#'.with(self.palette.get(i as usize))
+                }
+            })
+            .collect();
+        self.screen.update_with_style(grid);
+    }
+}
+
 fn flip(grid: &[Vec<u8>]) -> Vec<Vec<u8>> {
     let mut result = grid.to_vec();
     for (y, row) in grid.iter().enumerate() {
@@ -51,7 +88,7 @@ fn dfs(
     required_tiles: &mut Vec<usize>,
     max: (usize, usize),
     tile_id: &mut u64,
-    screen: &mut Screen,
+    screen: &mut AreaScreen,
     visualization: Visualization,
 ) -> bool {
     if required_tiles.iter().all(|f| *f == 0) {
@@ -100,7 +137,7 @@ fn dfs(
     false
 }
 
-pub fn visualize() {
+pub fn visualize(options: &Options) {
     let selections = &[
         "Insane     (even for areas that are too small, RUNS FOREVER!)",
         "Compact    (only for areas that provide enough space, SLOWER)",
@@ -119,7 +156,7 @@ pub fn visualize() {
         Visualization::Compact
     };
 
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+    let input = input::read_input_or_exit("input.txt");
 
     let blocks = input.split("\n\n").collect::<Vec<_>>();
     let raw_presents = &blocks[0..blocks.len() - 1];
@@ -183,7 +220,7 @@ pub fn visualize() {
         }
 
         if selection == 0 || req <= ar {
-            let mut screen = Screen::new(width, height);
+            let mut screen = AreaScreen::new(width, height, options);
 
             let mut area = vec![vec![0; width]; height];
             let mut tile = 0;
@@ -201,7 +238,7 @@ pub fn visualize() {
                 visualization,
             );
 
-            screen.finish();
+            screen.screen.finish();
 
             println!();
         }
@@ -0,0 +1,93 @@
+use std::{fs, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::divan::Timings;
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+/// The contents of Criterion's `estimates.json` (only the fields we need)
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+    median: Estimate,
+}
+
+/// The contents of Criterion's `sample.json`: the number of iterations and
+/// the total time (in nanoseconds) of each sample
+#[derive(Deserialize)]
+struct Sample {
+    iters: Vec<f64>,
+    times: Vec<f64>,
+}
+
+fn nanos(ns: f64) -> Duration {
+    Duration::from_nanos(ns.round() as u64)
+}
+
+/// Convert the contents of Criterion's `estimates.json` and `sample.json`
+/// files to timings. Criterion does not report the fastest and slowest
+/// iteration, so use the fastest and slowest sample instead (divided by their
+/// number of iterations).
+pub fn parse_results(estimates: &str, sample: &str) -> Result<Timings> {
+    let estimates: Estimates = serde_json::from_str(estimates)?;
+    let sample: Sample = serde_json::from_str(sample)?;
+
+    let per_iteration = sample
+        .times
+        .iter()
+        .zip(&sample.iters)
+        .map(|(t, i)| t / i)
+        .collect::<Vec<_>>();
+    let fastest = per_iteration.iter().copied().fold(f64::INFINITY, f64::min);
+    let slowest = per_iteration.iter().copied().fold(0.0, f64::max);
+
+    Ok(Timings {
+        fastest: nanos(fastest),
+        slowest: nanos(slowest),
+        median: nanos(estimates.median.point_estimate),
+        mean: nanos(estimates.mean.point_estimate),
+    })
+}
+
+/// Read the results of the benchmark with the given `name` from Criterion's
+/// output directory
+pub fn read_results(criterion_home: &Path, name: &str) -> Result<Timings> {
+    let dir = criterion_home.join(name).join("new");
+    let read = |file: &str| {
+        let path = dir.join(file);
+        fs::read_to_string(&path).with_context(|| format!("Unable to read {path:?}"))
+    };
+    parse_results(&read("estimates.json")?, &read("sample.json")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_results() {
+        let estimates = r#"{
+            "mean": {"confidence_interval": {}, "point_estimate": 596.8, "standard_error": 1.0},
+            "median": {"confidence_interval": {}, "point_estimate": 589.5, "standard_error": 1.0},
+            "median_abs_dev": {"confidence_interval": {}, "point_estimate": 3.0, "standard_error": 1.0},
+            "slope": null,
+            "std_dev": {"confidence_interval": {}, "point_estimate": 8.0, "standard_error": 1.0}
+        }"#;
+        let sample =
+            r#"{"sampling_mode": "Flat", "iters": [10.0, 20.0], "times": [6000.0, 11000.0]}"#;
+        assert_eq!(
+            parse_results(estimates, sample).unwrap(),
+            Timings {
+                fastest: Duration::from_nanos(550),
+                slowest: Duration::from_nanos(600),
+                median: Duration::from_nanos(590),
+                mean: Duration::from_nanos(597),
+            }
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+/// Timings of a single benchmark as reported by Divan
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timings {
+    pub fastest: Duration,
+    pub slowest: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+/// Parse a duration as printed by Divan (e.g. `302.5 ns`, `8.035 ms`)
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let (value, unit) = s.trim().split_once(' ')?;
+    let value = value.parse::<f64>().ok()?;
+    let factor = match unit.trim() {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(Duration::from_nanos((value * factor).round() as u64))
+}
+
+/// Format a duration with the largest unit that keeps the value at or above 1
+/// (e.g. `23µs`, `1.5ms`). Round to at most `precision` decimal places.
+pub fn format_duration(d: Duration, precision: usize) -> String {
+    let ns = d.as_nanos() as f64;
+    let (value, unit) = if ns >= 1e9 {
+        (ns / 1e9, "s")
+    } else if ns >= 1e6 {
+        (ns / 1e6, "ms")
+    } else if ns >= 1e3 {
+        (ns / 1e3, "µs")
+    } else {
+        (ns, "ns")
+    };
+    let value = format!("{value:.precision$}");
+    let value = if value.contains('.') {
+        value.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &value
+    };
+    format!("{value}{unit}")
+}
+
+/// Parse the output of a Divan benchmark run and return the name and timings
+/// of each benchmark. Divan prints one line per benchmark that looks like
+/// this:
+///
+/// ```text
+/// ╰─ day01  302.5 ns      │ 8.035 ms      │ 589.5 ns      │ 596.8 ns      │ 5933991 │ 5933991
+/// ```
+///
+/// Parameterized benchmarks are printed as a tree. Only the leaves (i.e. the
+/// individual cases) contain timings.
+pub fn parse_output(stdout: &str) -> Vec<(String, Timings)> {
+    let mut result = Vec::new();
+    for line in stdout.lines() {
+        let line = line.trim_start_matches([' ', '│']);
+        let Some(line) = line
+            .strip_prefix("├─ ")
+            .or_else(|| line.strip_prefix("╰─ "))
+        else {
+            continue;
+        };
+
+        let columns = line.split('│').map(str::trim).collect::<Vec<_>>();
+        if columns.len() < 4 {
+            continue;
+        }
+
+        // the first column contains the name of the benchmark followed by
+        // the fastest time
+        let Some((name, fastest)) = columns[0].split_once(char::is_whitespace) else {
+            continue;
+        };
+
+        let (Some(fastest), Some(slowest), Some(median), Some(mean)) = (
+            parse_duration(fastest),
+            parse_duration(columns[1]),
+            parse_duration(columns[2]),
+            parse_duration(columns[3]),
+        ) else {
+            continue;
+        };
+
+        result.push((
+            name.to_string(),
+            Timings {
+                fastest,
+                slowest,
+                median,
+                mean,
+            },
+        ));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("302.5 ns"), Some(Duration::from_nanos(303)));
+        assert_eq!(
+            parse_duration("8.035 ms"),
+            Some(Duration::from_nanos(8_035_000))
+        );
+        assert_eq!(
+            parse_duration("23.1 µs"),
+            Some(Duration::from_nanos(23_100))
+        );
+        assert_eq!(parse_duration("1.5 s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("5933991"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(23_100), 0), "23µs");
+        assert_eq!(format_duration(Duration::from_nanos(1_500_000), 1), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(2000), 2), "2s");
+        assert_eq!(format_duration(Duration::from_nanos(590), 1), "590ns");
+    }
+
+    #[test]
+    fn test_parse_output_with_cases() {
+        let stdout = "day25             fastest       │ slowest       │ median        │ mean          │ samples │ iters\n\
+            ╰─ day25                        │               │               │               │         │\n   \
+               ├─ input.txt  1.5 ms        │ 2 ms          │ 1.6 ms        │ 1.7 ms        │ 100     │ 100\n   \
+               ╰─ large.txt  15 ms         │ 20 ms         │ 16 ms         │ 17 ms         │ 100     │ 100\n";
+        let results = parse_output(stdout);
+        assert_eq!(
+            results.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(),
+            vec!["input.txt", "large.txt"]
+        );
+        assert_eq!(results[1].1.median, Duration::from_millis(16));
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let stdout = "Timer precision: 36 ns\n\
+            day01     fastest       │ slowest       │ median        │ mean          │ samples │ iters\n\
+            ╰─ day01  302.5 ns      │ 8.035 ms      │ 589.5 ns      │ 596.8 ns      │ 5933991 │ 5933991\n";
+        assert_eq!(
+            parse_output(stdout),
+            vec![(
+                "day01".to_string(),
+                Timings {
+                    fastest: Duration::from_nanos(303),
+                    slowest: Duration::from_nanos(8_035_000),
+                    median: Duration::from_nanos(590),
+                    mean: Duration::from_nanos(597),
+                }
+            )]
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    divan::{Timings, format_duration},
+    memory::Memory,
+};
+
+/// The result of benchmarking one project, as stored in the results file
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Record {
+    /// The path of the project relative to the root of the repository (e.g.
+    /// `2025/day01`)
+    pub project: String,
+
+    /// The name of the input file if the project was benchmarked with each
+    /// of its input files (see `--all-inputs`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+
+    /// The git commit the project was benchmarked at. Has the suffix `-dirty`
+    /// if the project had uncommitted changes.
+    pub commit: String,
+
+    /// The name of the machine the benchmark was performed on
+    pub machine: String,
+
+    /// An optional name under which the record can be found later
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+
+    pub fastest_ns: u64,
+    pub slowest_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+
+    /// The memory usage of the solution if it was measured (see `--memory`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Memory>,
+
+    /// The median time of each part of the solution (e.g. `part1`) if the
+    /// parts were timed separately (see `--parts`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parts_ns: BTreeMap<String, u64>,
+}
+
+impl Record {
+    pub fn new(
+        project: String,
+        input: Option<String>,
+        commit: String,
+        machine: String,
+        baseline: Option<String>,
+        timings: &Timings,
+        memory: Option<Memory>,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self {
+            project,
+            input,
+            commit,
+            machine,
+            baseline,
+            timestamp,
+            fastest_ns: timings.fastest.as_nanos() as u64,
+            slowest_ns: timings.slowest.as_nanos() as u64,
+            median_ns: timings.median.as_nanos() as u64,
+            mean_ns: timings.mean.as_nanos() as u64,
+            memory,
+            parts_ns: BTreeMap::new(),
+        }
+    }
+
+    /// The name of the project and (if available) of the input file, e.g.
+    /// `2018/day25 (large.txt)`
+    pub fn name(&self) -> String {
+        match &self.input {
+            Some(input) => format!("{} ({input})", self.project),
+            None => self.project.clone(),
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+/// Load all records from a results file (one JSON record per line). Return an
+/// empty list if the file does not exist yet.
+pub fn load(path: &Path) -> Result<Vec<Record>> {
+    if !fs::exists(path)? {
+        return Ok(Vec::new());
+    }
+    let mut result = Vec::new();
+    for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line)
+            .with_context(|| format!("Invalid record in {path:?} at line {}", i + 1))?;
+        result.push(record);
+    }
+    Ok(result)
+}
+
+/// Append records to a results file
+pub fn append(path: &Path, records: &[Record]) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for r in records {
+        writeln!(file, "{}", serde_json::to_string(r)?)?;
+    }
+    Ok(())
+}
+
+/// Find the most recent record of `project` from `machine` with the default
+/// input file: a record without an input file or one of `input.txt` recorded
+/// with `--all-inputs`
+pub fn find_latest_default<'a>(
+    history: &'a [Record],
+    project: &str,
+    machine: &str,
+) -> Option<&'a Record> {
+    history
+        .iter()
+        .filter(|r| r.project == project && r.machine == machine)
+        .filter(|r| r.input.as_deref().is_none_or(|i| i == "input.txt"))
+        .max_by_key(|r| r.timestamp)
+}
+
+/// Find the record to compare a new result of `project` and `input` with: the
+/// most recent record from the same machine with the given `baseline` name, or
+/// the most recent record from the same machine if no baseline is given.
+pub fn find_reference<'a>(
+    history: &'a [Record],
+    project: &str,
+    input: Option<&str>,
+    machine: &str,
+    baseline: Option<&str>,
+) -> Option<&'a Record> {
+    history
+        .iter()
+        .filter(|r| r.project == project && r.input.as_deref() == input && r.machine == machine)
+        .filter(|r| baseline.is_none() || r.baseline.as_deref() == baseline)
+        .max_by_key(|r| r.timestamp)
+}
+
+/// Compare two records of the same project by their median time
+pub struct Comparison<'a> {
+    pub before: &'a Record,
+    pub after: &'a Record,
+}
+
+impl Comparison<'_> {
+    /// The relative change of the median time in percent (positive values
+    /// mean the project got slower)
+    pub fn change(&self) -> f64 {
+        (self.after.median_ns as f64 / self.before.median_ns.max(1) as f64 - 1.0) * 100.0
+    }
+
+    /// Check if the project got slower by more than `threshold` percent
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+
+    /// Describe the comparison in a human-readable way
+    pub fn describe(&self, threshold: f64) -> String {
+        let change = self.change();
+        let verdict = if change > threshold {
+            "REGRESSION"
+        } else if change < -threshold {
+            "faster"
+        } else {
+            "no change"
+        };
+        format!(
+            "{}: {} ({}) -> {} ({}) {change:+.1}% {verdict}",
+            self.after.name(),
+            format_duration(self.before.median(), 1),
+            self.before.commit,
+            format_duration(self.after.median(), 1),
+            self.after.commit,
+        )
+    }
+}
+
+/// Run a git command in `dir` and return its trimmed output or `None` if it
+/// failed
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Get the root directory of the git repository containing `dir`
+pub fn repository_root(dir: &Path) -> Option<String> {
+    git(dir, &["rev-parse", "--show-toplevel"])
+}
+
+/// Get the current git commit of the project at `path`. Add the suffix
+/// `-dirty` if the project has uncommitted changes.
+pub fn commit_id(path: &Path) -> String {
+    let Some(commit) = git(path, &["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+    match git(path, &["status", "--porcelain", "."]) {
+        Some(s) if s.is_empty() => commit,
+        _ => format!("{commit}-dirty"),
+    }
+}
+
+/// Get the name of the current machine
+pub fn machine_name() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .or_else(|| {
+            let output = Command::new("hostname").output().ok()?;
+            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(project: &str, baseline: Option<&str>, timestamp: u64, median_ns: u64) -> Record {
+        Record {
+            project: project.to_string(),
+            input: None,
+            commit: "abc1234".to_string(),
+            machine: "m".to_string(),
+            baseline: baseline.map(|b| b.to_string()),
+            timestamp,
+            fastest_ns: median_ns,
+            slowest_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            memory: None,
+            parts_ns: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_reference() {
+        let history = vec![
+            record("2025/day01", Some("v1"), 1, 100),
+            record("2025/day01", None, 2, 200),
+            record("2025/day02", None, 3, 300),
+        ];
+        assert_eq!(
+            find_reference(&history, "2025/day01", None, "m", None).map(|r| r.median_ns),
+            Some(200)
+        );
+        assert_eq!(
+            find_reference(&history, "2025/day01", None, "m", Some("v1")).map(|r| r.median_ns),
+            Some(100)
+        );
+        assert!(find_reference(&history, "2025/day01", None, "other", None).is_none());
+        assert!(find_reference(&history, "2025/day03", None, "m", None).is_none());
+        assert!(find_reference(&history, "2025/day01", Some("input.txt"), "m", None).is_none());
+    }
+
+    #[test]
+    fn test_find_latest_default() {
+        let with_input = |input: &str, timestamp, median_ns| Record {
+            input: Some(input.to_string()),
+            ..record("2025/day01", None, timestamp, median_ns)
+        };
+        let mut history = vec![
+            record("2025/day01", None, 1, 100),
+            with_input("input.txt", 2, 200),
+            with_input("large.txt", 3, 300),
+        ];
+        let latest = |history: &[Record]| {
+            find_latest_default(history, "2025/day01", "m").map(|r| r.median_ns)
+        };
+        assert_eq!(latest(&history), Some(200));
+        history.push(record("2025/day01", None, 4, 400));
+        assert_eq!(latest(&history), Some(400));
+        assert_eq!(find_latest_default(&history, "2025/day01", "other"), None);
+    }
+
+    #[test]
+    fn test_comparison() {
+        let before = record("2025/day01", None, 1, 1000);
+        let after = record("2025/day01", None, 2, 1100);
+        let c = Comparison {
+            before: &before,
+            after: &after,
+        };
+        assert!((c.change() - 10.0).abs() < 1e-9);
+        assert!(c.is_regression(5.0));
+        assert!(!c.is_regression(10.5));
+        assert_eq!(
+            c.describe(5.0),
+            "2025/day01: 1µs (abc1234) -> 1.1µs (abc1234) +10.0% REGRESSION"
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let r = record("2025/day01", Some("v1"), 1, 1000);
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), r);
+        let r = record("2025/day01", None, 1, 1000);
+        assert!(!serde_json::to_string(&r).unwrap().contains("baseline"));
+    }
+}
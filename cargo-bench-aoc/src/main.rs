@@ -1,10 +1,28 @@
 use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
     fs::{self, File, FileTimes},
-    path::Path,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{self, ExitCode, ExitStatus, Stdio},
+    time::Duration,
 };
 
+mod criterion;
+mod divan;
+mod history;
+mod memory;
+mod parts;
+mod patch;
+mod readme;
+mod summary;
+
 use anyhow::{Context, Result, bail};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use divan::Timings;
+use history::{Comparison, Record};
+use memory::Memory;
+use summary::{Skipped, Summary};
 use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, value};
 use walkdir::WalkDir;
 
@@ -18,15 +36,228 @@ struct Args {
 #[derive(Subcommand)]
 enum Command {
     BenchAoc {
-        /// Paths of the projects to benchmark
-        #[arg(required = true)]
+        /// Paths of the projects to benchmark. May also be directories of
+        /// years (e.g. `2024`) or glob patterns (e.g. `2023/day2*`).
+        #[arg(required_unless_present_any = ["years", "update_readme"])]
         paths: Vec<String>,
+
+        /// Benchmark all days of the given year (may be repeated)
+        #[arg(long = "year", value_name = "YEAR")]
+        years: Vec<u32>,
+
+        /// Regenerate the benchmark tables in the repository's README.md from
+        /// the latest results recorded on this machine
+        #[arg(long)]
+        update_readme: bool,
+
+        /// File to store the results in (one JSON record per line). Default:
+        /// `bench-aoc-results.jsonl` in the root of the git repository.
+        #[arg(long)]
+        results: Option<PathBuf>,
+
+        /// Store the results under the given baseline name
+        #[arg(long, value_name = "NAME")]
+        save_baseline: Option<String>,
+
+        /// Compare the results with the previous run on this machine or with
+        /// the given baseline
+        #[arg(long, value_name = "BASELINE", num_args = 0..=1, require_equals = true)]
+        compare: Option<Option<String>>,
+
+        /// Fail if the median time of a project increased by more than this
+        /// percentage (only with --compare)
+        #[arg(long, value_name = "PERCENT", default_value_t = 5.0)]
+        threshold: f64,
+
+        /// Write a summary of all results to the given file. The format
+        /// depends on the extension (`.csv` or `.json`).
+        #[arg(long, value_name = "FILE")]
+        summary: Option<PathBuf>,
+
+        #[command(flatten)]
+        options: BenchOptions,
     },
 }
 
+/// The benchmarking library to use
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Backend {
+    Divan,
+    Criterion,
+}
+
+/// Options that are passed through to `cargo bench` and the benchmarking
+/// library
+#[derive(clap::Args)]
+struct BenchOptions {
+    /// The benchmarking library to use
+    #[arg(long, value_enum, default_value_t = Backend::Divan)]
+    backend: Backend,
+
+    /// Minimum time to spend benchmarking each project (in seconds)
+    #[arg(long, value_name = "SECONDS", default_value_t = 5.0)]
+    min_time: f64,
+
+    /// Maximum time to spend benchmarking each project (in seconds). Not
+    /// supported by Criterion.
+    #[arg(long, value_name = "SECONDS", default_value_t = 60.0)]
+    max_time: f64,
+
+    /// Number of samples to collect (default: chosen by the backend)
+    #[arg(long, value_name = "N")]
+    sample_count: Option<u32>,
+
+    /// Number of iterations per sample (default: chosen by Divan). Not
+    /// supported by Criterion.
+    #[arg(long, value_name = "N")]
+    sample_size: Option<u32>,
+
+    /// Comma-separated list of features to activate
+    #[arg(long, short = 'F')]
+    features: Option<String>,
+
+    /// Do not activate the `default` feature
+    #[arg(long)]
+    no_default_features: bool,
+
+    /// Override the link-time optimization setting of the bench profile
+    /// (e.g. `fat`, `thin`, or `off`)
+    #[arg(long, value_name = "LTO")]
+    lto: Option<String>,
+
+    /// Override the number of codegen units of the bench profile
+    #[arg(long, value_name = "N")]
+    codegen_units: Option<u32>,
+
+    /// Compile for the CPU of this machine (`-C target-cpu=native`)
+    #[arg(long)]
+    native: bool,
+
+    /// Benchmark each input file (all `*.txt` files in the project
+    /// directory) as a separate case
+    #[arg(long)]
+    all_inputs: bool,
+
+    /// Validate the answers before running the benchmark. The expected
+    /// answers for `NAME.txt` are read from `NAME.answers` or recorded with
+    /// the unpatched solution if this file does not exist.
+    #[arg(long)]
+    validate: bool,
+
+    /// Keep the answers recorded for --validate as `NAME.answers` in the
+    /// project directory, so later runs compare with them. By default, they
+    /// are only written to the benchmark's directory in `target`.
+    #[arg(long, requires = "validate")]
+    record_answers: bool,
+
+    /// Measure the number of allocations, the number of bytes allocated and
+    /// the peak memory usage of the solution. Installs a counting allocator,
+    /// which adds a small overhead to the timings.
+    #[arg(long)]
+    memory: bool,
+
+    /// Time parsing, part 1 and part 2 of the solution separately. The parts
+    /// are recognized by comments starting with `// parse`, `// part 1`, and
+    /// `// part 2` at the top level of the main function.
+    #[arg(long)]
+    parts: bool,
+}
+
+impl BenchOptions {
+    /// Create a `cargo bench` command for the project copied to
+    /// `bench_aoc_path` with all features and profile overrides applied
+    fn cargo_bench(&self, bench_aoc_path: &Path) -> process::Command {
+        let mut command = self.cargo("bench", bench_aoc_path);
+        command.args(["--target-dir", ".."]);
+        command
+    }
+
+    /// Create a cargo command that runs `subcommand` in the directory `dir`
+    /// with all features and overrides of the bench profile applied
+    fn cargo(&self, subcommand: &str, dir: &Path) -> process::Command {
+        let mut command = process::Command::new("cargo");
+        command.arg(subcommand).current_dir(dir);
+        if let Some(features) = &self.features {
+            command.args(["--features", features]);
+        }
+        if self.no_default_features {
+            command.arg("--no-default-features");
+        }
+        if let Some(lto) = &self.lto {
+            command.args(["--config", &format!("profile.bench.lto={lto:?}")]);
+        }
+        if let Some(codegen_units) = self.codegen_units {
+            command.args([
+                "--config",
+                &format!("profile.bench.codegen-units={codegen_units}"),
+            ]);
+        }
+        if self.native {
+            command.args([
+                "--config",
+                "build.rustflags=[\"-C\", \"target-cpu=native\"]",
+            ]);
+        }
+        command
+    }
+
+    /// Arguments for the benchmarking library
+    fn backend_args(&self) -> Vec<String> {
+        let mut result = Vec::new();
+        match self.backend {
+            Backend::Divan => {
+                result.extend([
+                    "--min-time".to_string(),
+                    self.min_time.to_string(),
+                    "--max-time".to_string(),
+                    self.max_time.to_string(),
+                ]);
+                if let Some(sample_count) = self.sample_count {
+                    result.extend(["--sample-count".to_string(), sample_count.to_string()]);
+                }
+                if let Some(sample_size) = self.sample_size {
+                    result.extend(["--sample-size".to_string(), sample_size.to_string()]);
+                }
+            }
+            Backend::Criterion => {
+                result.extend([
+                    "--noplot".to_string(),
+                    "--measurement-time".to_string(),
+                    self.min_time.to_string(),
+                ]);
+                if let Some(sample_count) = self.sample_count {
+                    result.extend(["--sample-size".to_string(), sample_count.to_string()]);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Printed by the benchmark (followed by the name of the file with the
+/// expected answers) if the answers are wrong. The benchmark then exits, and
+/// the project is skipped.
+const WRONG_ANSWERS: &str = "Wrong answers";
+
+/// Error returned if `cargo bench` fails
+#[derive(Debug)]
+struct CargoError {
+    message: &'static str,
+    status: ExitStatus,
+}
+
+impl Display for CargoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.status)
+    }
+}
+
+impl std::error::Error for CargoError {}
+
 /// Copy a project from one directory to another. Skip the `target` directory,
-/// `Cargo.toml`, and `main.rs`. Also skip `Cargo.lock` unless it does not exist
-/// in the destination directory yet.
+/// `Cargo.toml`, and all Rust source files (they will be patched separately).
+/// Also skip `Cargo.lock` unless it does not exist in the destination
+/// directory yet.
 fn copy_project(src: &Path, dest: &Path) -> Result<()> {
     let target_path = src.join("target");
     for entry in WalkDir::new(src).into_iter().filter_entry(|e| {
@@ -35,8 +266,8 @@ fn copy_project(src: &Path, dest: &Path) -> Result<()> {
             (e.file_type().is_dir() && e.path() == target_path) ||
             // skip Cargo.toml
             e.file_name() == "Cargo.toml" ||
-            // skip main.rs
-            e.file_name() == "main.rs"
+            // skip Rust source files
+            (e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "rs"))
         )
     }) {
         let entry = entry?;
@@ -72,63 +303,33 @@ fn write_file_if_necessary(file_path: &Path, contents: &str) -> Result<()> {
     Ok(())
 }
 
-/// Replace the `main` function in a `main.rs` contents string with
-/// `__bench_aoc_main`.
-fn replace_main_fn(main_rs: &str, benchmark_name: String) -> Result<String> {
-    let Some(main_fn_pos) = main_rs.find("fn main() {") else {
-        bail!("Unable to find main function in main.rs");
-    };
-    let mut result = String::new();
-    result.push_str(&main_rs[..main_fn_pos]);
-    result.push_str(&format!(
-        "#[divan::bench(name = \"{benchmark_name}\")]\nfn __bench_aoc_main() {{"
-    ));
-    result.push_str(&main_rs[main_fn_pos + 11..]);
-    Ok(result)
-}
-
-/// Replace all `fs::read_to_string` calls in a `main.rs` contents string with
-/// `__aoc_bench_read_to_string`.
-fn replace_read_input(main_rs: &str) -> String {
-    main_rs.replace("fs::read_to_string", "__aoc_bench_read_to_string")
-}
-
-/// Replace all `println!` calls in a `main.rs` contents string with `format!`
-/// calls. Wrap them in [std::hint::black_box] so they won't be optimized away.
-fn replace_printlns(main_rs: &str) -> String {
-    let mut result = String::new();
-    let mut last_end = 0;
-    for (start, part) in main_rs.match_indices("println!(") {
-        result.push_str(&main_rs[last_end..start]);
-        let mut depth = 1;
-        let mut skipped = 0;
-        for c in main_rs[start + part.len()..].chars() {
-            skipped += 1;
-            if c == '(' {
-                depth += 1;
-            } else if c == ')' {
-                depth -= 1;
-            }
-            if depth == 0 {
-                break;
-            }
+/// Add boilerplate code for Criterion to a `main.rs` contents string. If
+/// `inputs` is given, benchmark the main function once per input file.
+fn add_criterion_boilerplate(
+    main_rs: &mut String,
+    benchmark_name: &str,
+    inputs: Option<&[String]>,
+) {
+    let body = match inputs {
+        None => {
+            format!(r#"    c.bench_function("{benchmark_name}", |b| b.iter(__bench_aoc_main));"#)
         }
-        last_end = start + part.len() + skipped;
-        result.push_str("let _ = std::hint::black_box(format!(");
-        result.push_str(&main_rs[start + part.len()..last_end]);
-        result.push(')');
-    }
-    result.push_str(&main_rs[last_end..]);
-    result
-}
-
-/// Add boilerplate code for Criterion to a `main.rs` contents string
-#[allow(unused)]
-fn add_criterion_boilerplate(main_rs: &mut String, benchmark_name: String) {
+        Some(inputs) => format!(
+            r#"    let mut group = c.benchmark_group("{benchmark_name}");
+    for input in {inputs:?} {{
+        __AOC_BENCH_INPUT.set(__aoc_bench_input(input));
+        group.bench_function(input, |b| b.iter(__bench_aoc_main));
+    }}
+    group.finish();"#
+        ),
+    };
     main_rs.push_str(&format!(
         r#"
 fn __aoc_bench_criterion_benchmark(c: &mut criterion::Criterion) {{
-    c.bench_function("{benchmark_name}", |b| b.iter(__bench_aoc_main));
+    __aoc_bench_validate();
+    __aoc_bench_profile_memory();
+    __aoc_bench_time_parts();
+{body}
 }}
 
 criterion::criterion_group!(__aoc_bench, __aoc_bench_criterion_benchmark);
@@ -137,11 +338,28 @@ criterion::criterion_main!(__aoc_bench);
     ));
 }
 
-/// Add boilerplate code for Divan to a `main.rs` contents string
-fn add_divan_boilerplate(main_rs: &mut String) {
+/// Add boilerplate code for Divan to a `main.rs` contents string. If `inputs`
+/// is given, add a benchmark that runs the main function once per input file
+/// (as parameterized cases).
+fn add_divan_boilerplate(main_rs: &mut String, benchmark_name: &str, inputs: Option<&[String]>) {
+    if let Some(inputs) = inputs {
+        main_rs.push_str(&format!(
+            r#"
+#[divan::bench(name = "{benchmark_name}", args = {inputs:?})]
+fn __bench_aoc_inputs(bencher: divan::Bencher, input: &str) {{
+    __AOC_BENCH_INPUT.set(__aoc_bench_input(input));
+    bencher.bench_local(__bench_aoc_main);
+}}
+"#
+        ));
+    }
+
     main_rs.push_str(
         r#"
 fn main() {{
+    __aoc_bench_validate();
+    __aoc_bench_profile_memory();
+    __aoc_bench_time_parts();
     divan::main();
 }}
 "#,
@@ -149,40 +367,374 @@ fn main() {{
 }
 
 /// Add boilerplate code that reads input files
-fn add_read_input_boilerplate(main_rs: &mut String, input_files: Vec<String>) {
+fn add_read_input_boilerplate(main_rs: &mut String, input_files: &[String]) {
     main_rs.push_str(
         r#"
-#[inline(always)]
-fn __aoc_bench_read_to_string(path: &str) -> std::io::Result<String> {
+thread_local! {
+    /// The contents of the input file the current benchmark case should use
+    /// instead of the one the solution reads
+    static __AOC_BENCH_INPUT: std::cell::Cell<Option<&'static str>> =
+        const { std::cell::Cell::new(None) };
+}
+
+/// Get the contents of an inlined input file
+fn __aoc_bench_input(name: &str) -> Option<&'static str> {
+    match name {
 "#,
     );
 
     // inline known input files
     for name in input_files {
         main_rs.push_str(&format!(
-            r#"
-        if path == "{name}" {{
-            Ok(include_str!("../{name}").to_string())
-        }} else
-    "#,
+            r#"        {name:?} => Some(include_str!("../{name}")),
+"#,
         ));
     }
 
     // fallback if an input file could not be inlined
+    main_rs.push_str(
+        r#"        _ => None,
+    }
+}
+
+#[inline(always)]
+fn __aoc_bench_read_to_string(path: impl AsRef<std::path::Path>) -> std::io::Result<String> {
+    let path = path.as_ref();
+    if let Some(input) = __AOC_BENCH_INPUT.get() {
+        return Ok(input.to_string());
+    }
+    if let Some(input) = path.to_str().and_then(__aoc_bench_input) {
+        return Ok(input.to_string());
+    }
+    eprintln!("Warning: Reading input from {}", path.display());
+    std::fs::read_to_string(path)
+}
+"#,
+    );
+}
+
+/// Add boilerplate code that captures the answers the solution prints and
+/// validates them before the benchmark starts. `cases` contains the name of
+/// the input file to select (or `None` to let the solution read its default
+/// input file) and the path of the file with the expected answers (relative
+/// to the project directory).
+fn add_validation_boilerplate(main_rs: &mut String, cases: &[(Option<String>, String)]) {
+    main_rs.push_str(
+        r#"
+thread_local! {
+    /// The answers printed by the solution (if they should be captured)
+    static __AOC_BENCH_OUTPUT: std::cell::RefCell<Option<String>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+#[inline(always)]
+fn __aoc_bench_print(s: String, newline: bool) {
+    __AOC_BENCH_OUTPUT.with_borrow_mut(|output| {
+        if let Some(output) = output {
+            output.push_str(&s);
+            if newline {
+                output.push('\n');
+            }
+        }
+    });
+    std::hint::black_box(s);
+}
+
+/// Run the solution once per case, compare the answers it prints with the
+/// expected ones, and exit if they differ
+fn __aoc_bench_validate() {
+    let cases: &[(Option<&str>, &str, &str)] = &[
+"#,
+    );
+
+    for (input, answers) in cases {
+        main_rs.push_str(&format!(
+            "        ({input:?}, {answers:?}, include_str!(\"../{answers}\")),\n"
+        ));
+    }
+    main_rs.push_str(&format!(
+        "    ];\n    const WRONG_ANSWERS: &str = {WRONG_ANSWERS:?};\n"
+    ));
+
+    main_rs.push_str(
+        r#"
+    for (input, answers, expected) in cases {
+        __AOC_BENCH_INPUT.set(input.and_then(__aoc_bench_input));
+        __AOC_BENCH_OUTPUT.set(Some(String::new()));
+        let _ = __bench_aoc_main();
+        let actual = __AOC_BENCH_OUTPUT.take().unwrap_or_default();
+        __AOC_BENCH_INPUT.set(None);
+
+        if actual != *expected {
+            println!("{WRONG_ANSWERS} (expected: {answers})");
+            let expected = expected.lines().collect::<Vec<_>>();
+            let actual = actual.lines().collect::<Vec<_>>();
+            for i in 0..expected.len().max(actual.len()) {
+                let (e, a) = (expected.get(i), actual.get(i));
+                if e == a {
+                    eprintln!(" {}", e.unwrap());
+                    continue;
+                }
+                if let Some(e) = e {
+                    eprintln!("-{e}");
+                }
+                if let Some(a) = a {
+                    eprintln!("+{a}");
+                }
+            }
+            std::process::exit(1);
+        }
+    }
+}
+"#,
+    );
+}
+
+/// Add boilerplate code that measures the memory usage of the solution with a
+/// counting global allocator and prints it once per input file (or once if
+/// `inputs` is `None`). If `enabled` is false, the allocator is not installed
+/// and nothing is measured.
+fn add_memory_boilerplate(main_rs: &mut String, inputs: Option<&[String]>, enabled: bool) {
+    if !enabled {
+        main_rs.push_str("\nfn __aoc_bench_profile_memory() {}\n");
+        return;
+    }
+
     main_rs.push_str(
         r#"
-    {
-        eprintln!("Warning: Reading input from {path}");
-        fs::read_to_string(path)
+static __AOC_BENCH_ALLOCATIONS: std::sync::atomic::AtomicU64 =
+    std::sync::atomic::AtomicU64::new(0);
+static __AOC_BENCH_ALLOCATED: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+static __AOC_BENCH_CURRENT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+static __AOC_BENCH_PEAK: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// A global allocator that counts allocations and keeps track of the peak
+/// memory usage
+struct __AocBenchAllocator;
+
+impl __AocBenchAllocator {
+    #[inline(always)]
+    fn record(old_size: usize, new_size: usize) {
+        use std::sync::atomic::Ordering::Relaxed;
+        __AOC_BENCH_ALLOCATIONS.fetch_add(1, Relaxed);
+        __AOC_BENCH_ALLOCATED.fetch_add(new_size as u64, Relaxed);
+        // while reallocating, the old and the new memory block may both be alive
+        let current = __AOC_BENCH_CURRENT.fetch_add(new_size as u64, Relaxed) + new_size as u64;
+        __AOC_BENCH_PEAK.fetch_max(current, Relaxed);
+        __AOC_BENCH_CURRENT.fetch_sub(old_size as u64, Relaxed);
     }
 }
+
+unsafe impl std::alloc::GlobalAlloc for __AocBenchAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = unsafe { std::alloc::System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let ptr = unsafe { std::alloc::System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record(0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) };
+        __AOC_BENCH_CURRENT.fetch_sub(layout.size() as u64, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: std::alloc::Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new_ptr = unsafe { std::alloc::System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::record(layout.size(), new_size);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static __AOC_BENCH_GLOBAL_ALLOCATOR: __AocBenchAllocator = __AocBenchAllocator;
+
+/// Run the solution once per input file and print its memory usage
+fn __aoc_bench_profile_memory() {
+    use std::sync::atomic::Ordering::Relaxed;
 "#,
     );
+
+    let cases = match inputs {
+        Some(inputs) => inputs.iter().map(|i| Some(i.as_str())).collect(),
+        None => vec![None],
+    };
+    main_rs.push_str(&format!("    let cases: &[Option<&str>] = &{cases:?};\n"));
+
+    main_rs.push_str(&format!(
+        r#"    for input in cases {{
+        __AOC_BENCH_INPUT.set(input.and_then(__aoc_bench_input));
+        let current = __AOC_BENCH_CURRENT.load(Relaxed);
+        __AOC_BENCH_ALLOCATIONS.store(0, Relaxed);
+        __AOC_BENCH_ALLOCATED.store(0, Relaxed);
+        __AOC_BENCH_PEAK.store(current, Relaxed);
+        let _ = __bench_aoc_main();
+        let allocations = __AOC_BENCH_ALLOCATIONS.load(Relaxed);
+        let allocated = __AOC_BENCH_ALLOCATED.load(Relaxed);
+        let peak = __AOC_BENCH_PEAK.load(Relaxed) - current;
+        __AOC_BENCH_INPUT.set(None);
+
+        let name = input.map(|i| format!(" ({{i}})")).unwrap_or_default();
+        println!(
+            "{prefix}{{name}}: {{allocations}} allocations, {{allocated}} bytes allocated, {{peak}} bytes peak"
+        );
+    }}
+}}
+"#,
+        prefix = memory::OUTPUT_PREFIX
+    ));
+}
+
+/// Add boilerplate code that times the parts of the solution (see
+/// [parts::insert_markers]) and prints the median time of each part once per
+/// input file (or once if `inputs` is `None`). If `enabled` is false, nothing
+/// is timed.
+fn add_parts_boilerplate(main_rs: &mut String, inputs: Option<&[String]>, enabled: bool) {
+    if !enabled {
+        main_rs.push_str("\nfn __aoc_bench_time_parts() {}\n");
+        return;
+    }
+
+    main_rs.push_str(
+        r#"
+thread_local! {
+    /// The times of the parts measured in the current run and the part that is
+    /// currently running (if parts should be timed)
+    static __AOC_BENCH_PARTS: std::cell::RefCell<
+        Option<(
+            Vec<(&'static str, std::time::Duration)>,
+            Option<(&'static str, std::time::Instant)>,
+        )>,
+    > = const { std::cell::RefCell::new(None) };
+}
+
+/// Stop timing the current part of the solution and start timing the given
+/// one. An empty `part` only stops timing the current part.
+#[inline(always)]
+fn __aoc_bench_part(part: &'static str) {
+    __AOC_BENCH_PARTS.with_borrow_mut(|parts| {
+        if let Some((times, current)) = parts {
+            let now = std::time::Instant::now();
+            if let Some((name, start)) = current.take() {
+                times.push((name, now - start));
+            }
+            *current = (!part.is_empty()).then_some((part, now));
+        }
+    });
+}
+
+/// Run the solution repeatedly (at least 10 times and for about a second) per
+/// input file and print the median time of each of its parts
+fn __aoc_bench_time_parts() {
+"#,
+    );
+
+    let cases = match inputs {
+        Some(inputs) => inputs.iter().map(|i| Some(i.as_str())).collect(),
+        None => vec![None],
+    };
+    let names = parts::PARTS.map(|(name, _)| name);
+    main_rs.push_str(&format!(
+        r#"    let cases: &[Option<&str>] = &{cases:?};
+    for input in cases {{
+        __AOC_BENCH_INPUT.set(input.and_then(__aoc_bench_input));
+        let mut runs = Vec::new();
+        let start = std::time::Instant::now();
+        while runs.len() < 10
+            || (runs.len() < 10_000 && start.elapsed() < std::time::Duration::from_secs(1))
+        {{
+            __AOC_BENCH_PARTS.set(Some((Vec::new(), None)));
+            let _ = __bench_aoc_main();
+            __aoc_bench_part("");
+            runs.push(__AOC_BENCH_PARTS.take().unwrap().0);
+        }}
+        __AOC_BENCH_INPUT.set(None);
+
+        let name = input.map(|i| format!(" ({{i}})")).unwrap_or_default();
+        for part in {names:?} {{
+            if !runs.iter().any(|r| r.iter().any(|(p, _)| *p == part)) {{
+                continue;
+            }}
+            let mut times = runs
+                .iter()
+                .map(|r| {{
+                    r.iter()
+                        .filter(|(p, _)| *p == part)
+                        .map(|(_, t)| *t)
+                        .sum::<std::time::Duration>()
+                }})
+                .collect::<Vec<_>>();
+            times.sort();
+            println!(
+                "{prefix} {{part}}{{name}}: {{}} ns",
+                times[times.len() / 2].as_nanos()
+            );
+        }}
+    }}
+}}
+"#,
+        prefix = parts::OUTPUT_PREFIX
+    ));
+}
+
+/// Get the name of the file with the expected answers for the given input
+/// file (e.g. `input.answers` for `input.txt`)
+fn answers_file_name(input: &str) -> String {
+    let stem = Path::new(input)
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy();
+    format!("{stem}.answers")
+}
+
+/// Run the unpatched solution of the project at `path` with the given input
+/// file (built like the benchmark) and record its output as the expected
+/// answers. Write them to the copied project directory at `bench_aoc_path`
+/// and, if `--record-answers` is given, to the project directory. Return the
+/// name of the file the answers have been written to.
+fn record_answers(
+    path: &Path,
+    bench_aoc_path: &Path,
+    input: &str,
+    options: &BenchOptions,
+) -> Result<String> {
+    let answers = answers_file_name(input);
+    println!("--------- Recording answers for {input} in {answers} ...");
+
+    let output = options
+        .cargo("run", path)
+        .args(["--profile", "bench", "--quiet", "--", input])
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        bail!("Unable to record answers ({})", output.status);
+    }
+
+    fs::write(bench_aoc_path.join(&answers), &output.stdout)?;
+    if options.record_answers {
+        fs::write(path.join(&answers), &output.stdout)?;
+    }
+    Ok(answers)
 }
 
 /// Find all possible input files in the project directory at `path` and return
-/// their names. Do not decent into subdirectories. All files with the extension
-/// `.txt` are considered input files.
+/// their names in alphabetical order. Do not decent into subdirectories. All
+/// files with the extension `.txt` are considered input files.
 fn find_input_files(path: &Path) -> Result<Vec<String>> {
     let mut result = Vec::new();
     for entry in WalkDir::new(path).max_depth(1) {
@@ -191,32 +743,133 @@ fn find_input_files(path: &Path) -> Result<Vec<String>> {
             result.push(entry.file_name().to_string_lossy().to_string());
         }
     }
+    result.sort();
     Ok(result)
 }
 
 /// Read the `src/main.rs` file from the project directory at `path`, patch it,
 /// and write the results to the copied project directory at `bench_aoc_path`.
-fn patch_main_rs(path: &Path, bench_aoc_path: &Path, benchmark_name: String) -> Result<()> {
+/// If `--all-inputs` is given, benchmark the project once per input file.
+/// Return the names of these input files.
+fn patch_main_rs(
+    path: &Path,
+    bench_aoc_path: &Path,
+    benchmark_name: &str,
+    options: &BenchOptions,
+) -> Result<Vec<String>> {
     let orig_main_rs_path = path.join("src").join("main.rs");
     let dest_main_rs_path = bench_aoc_path.join("src").join("__bench_aoc_main.rs");
-    let mut main_rs = fs::read_to_string(&orig_main_rs_path)?;
-
-    main_rs = replace_main_fn(&main_rs, benchmark_name)?;
-    main_rs = replace_read_input(&main_rs);
-    main_rs = replace_printlns(&main_rs);
-    add_divan_boilerplate(&mut main_rs);
+    let main_rs = fs::read_to_string(&orig_main_rs_path)?;
 
     let input_files = find_input_files(path)?;
-    add_read_input_boilerplate(&mut main_rs, input_files);
+    if options.all_inputs && input_files.is_empty() {
+        bail!("Unable to find any input files in {path:?}");
+    }
+    let inputs = options.all_inputs.then_some(input_files.as_slice());
+
+    // collect the expected answers of each case and record missing ones
+    let mut cases = Vec::new();
+    if options.validate {
+        let validated_inputs = match inputs {
+            Some(inputs) => inputs.iter().map(|i| Some(i.clone())).collect(),
+            None => vec![None],
+        };
+        for input in validated_inputs {
+            let file = input.as_deref().unwrap_or("input.txt");
+            if !fs::exists(path.join(file))? {
+                bail!("Unable to validate answers: {file} does not exist");
+            }
+            let mut answers = answers_file_name(file);
+            if !fs::exists(path.join(&answers))? {
+                answers = record_answers(path, bench_aoc_path, file, options)?;
+            }
+            cases.push((input, answers));
+        }
+    }
+
+    let attribute = match options.backend {
+        Backend::Divan if inputs.is_none() => {
+            Some(format!("#[divan::bench(name = {benchmark_name:?})]"))
+        }
+        _ => None,
+    };
+
+    // insert markers for the parts of the solution if they should be timed
+    let marked_main_rs = if options.parts {
+        let marked = parts::insert_markers(&main_rs);
+        if marked.is_none() {
+            bail!(
+                "Unable to time the parts of the solution: {orig_main_rs_path:?} has no \
+                `// parse`, `// part 1`, or `// part 2` comments at the top level of `fn main`"
+            );
+        }
+        marked
+    } else {
+        None
+    };
+    let marked_main_rs = marked_main_rs.and_then(|m| {
+        patch::patch_main(&m, attribute.as_deref())
+            .inspect_err(|e| {
+                eprintln!("Warning: Unable to insert markers for the parts of the solution: {e}")
+            })
+            .ok()
+    });
+    let time_parts = marked_main_rs.is_some();
+
+    let mut main_rs = match marked_main_rs {
+        Some(main_rs) => main_rs,
+        None => patch::patch_main(&main_rs, attribute.as_deref())
+            .with_context(|| format!("Unable to patch {orig_main_rs_path:?}"))?,
+    };
+    match options.backend {
+        Backend::Divan => add_divan_boilerplate(&mut main_rs, benchmark_name, inputs),
+        Backend::Criterion => add_criterion_boilerplate(&mut main_rs, benchmark_name, inputs),
+    }
+
+    add_read_input_boilerplate(&mut main_rs, &input_files);
+    add_validation_boilerplate(&mut main_rs, &cases);
+    add_memory_boilerplate(&mut main_rs, inputs, options.memory);
+    add_parts_boilerplate(&mut main_rs, inputs, time_parts);
 
-    write_file_if_necessary(&dest_main_rs_path, &main_rs)
+    write_file_if_necessary(&dest_main_rs_path, &main_rs)?;
+
+    Ok(if options.all_inputs {
+        input_files
+    } else {
+        Vec::new()
+    })
+}
+
+/// Read all Rust source files except `main.rs` from the `src` directory of the
+/// project at `path` (e.g. helper modules such as `visualize.rs`), patch them,
+/// and write the results to the copied project directory at `bench_aoc_path`.
+fn patch_modules(path: &Path, bench_aoc_path: &Path) -> Result<()> {
+    let src_path = path.join("src");
+    for entry in WalkDir::new(&src_path) {
+        let entry = entry?;
+        if !entry.file_type().is_file()
+            || entry.path().extension().is_none_or(|ext| ext != "rs")
+            || entry.path() == src_path.join("main.rs")
+        {
+            continue;
+        }
+
+        let module = fs::read_to_string(entry.path())?;
+        let module = patch::patch_module(&module)
+            .with_context(|| format!("Unable to patch {:?}", entry.path()))?;
+
+        let relative = entry.path().strip_prefix(path)?;
+        write_file_if_necessary(&bench_aoc_path.join(relative), &module)?;
+    }
+    Ok(())
 }
 
 /// Read the `Cargo.toml` file from the project directory at `path`, patch it,
 /// and write the results to the copied project directory at `bench_aoc_path`.
-/// Adds `Divan` to the dependencies and adds the configuration for `cargo
-/// bench`. Also converts all relative dependency paths to absolute ones.
-fn patch_cargo_toml(path: &Path, bench_aoc_path: &Path) -> Result<String> {
+/// Adds the benchmarking library (`Divan` or `Criterion`) to the dependencies
+/// and adds the configuration for `cargo bench`. Also converts all relative
+/// dependency paths to absolute ones.
+fn patch_cargo_toml(path: &Path, bench_aoc_path: &Path, backend: Backend) -> Result<String> {
     let orig_cargo_toml_path = path.join("Cargo.toml");
     let dest_cargo_toml_path = bench_aoc_path.join("Cargo.toml");
     let cargo_toml = fs::read_to_string(&orig_cargo_toml_path)?;
@@ -232,7 +885,16 @@ fn patch_cargo_toml(path: &Path, bench_aoc_path: &Path) -> Result<String> {
         .or_insert(Table::default().into())
         .as_table_mut()
         .unwrap();
-    dependencies_table["divan"] = value("0");
+    match backend {
+        Backend::Divan => {
+            dependencies_table.remove("criterion");
+            dependencies_table["divan"] = value("0");
+        }
+        Backend::Criterion => {
+            dependencies_table.remove("divan");
+            dependencies_table["criterion"] = value("0");
+        }
+    }
 
     for (_, v) in dependencies_table.iter_mut() {
         if let Some(t) = v.as_table_like_mut()
@@ -261,10 +923,25 @@ fn patch_cargo_toml(path: &Path, bench_aoc_path: &Path) -> Result<String> {
     Ok(benchmark_name)
 }
 
-/// Benchmark a project at the given `path`
-fn bench(path: &str) -> Result<()> {
-    let path = std::path::PathBuf::from(path);
+/// The results of benchmarking a project with one input file
+struct BenchResult {
+    /// The name of the input file (only if `--all-inputs` is given)
+    input: Option<String>,
+    timings: Timings,
 
+    /// The memory usage (only if `--memory` is given)
+    memory: Option<Memory>,
+
+    /// The median time of each part of the solution (only if `--parts` is
+    /// given)
+    parts: BTreeMap<String, Duration>,
+}
+
+/// Benchmark a project at the given `path` and return the timings the
+/// benchmarking library reported (and the memory usage and the times of the
+/// parts if `--memory` or `--parts` is given). If `--all-inputs` is given,
+/// return the results of each input file together with its name.
+fn bench(path: &Path, options: &BenchOptions) -> Result<Vec<BenchResult>> {
     // look for Cargo.toml
     if !fs::exists(path.join("Cargo.toml"))? {
         bail!("Unable to find Cargo.toml in path {path:?}");
@@ -280,38 +957,363 @@ fn bench(path: &str) -> Result<()> {
     let bench_aoc_path = target_path.join("bench-aoc");
     fs::create_dir_all(&bench_aoc_path)?;
 
-    copy_project(&path, &bench_aoc_path)?;
-
-    let benchmark_name = patch_cargo_toml(&path, &bench_aoc_path)?;
-    patch_main_rs(&path, &bench_aoc_path, benchmark_name)?;
-
-    println!("--------- Running benchmark for at least 5s and no more than 60s ...");
-
-    let mut process = std::process::Command::new("cargo")
-        .args([
-            "bench",
-            "--target-dir",
-            "..",
-            "--",
-            "--min-time",
-            "5",
-            "--max-time",
-            "60",
-        ])
-        .current_dir(&bench_aoc_path)
+    copy_project(path, &bench_aoc_path)?;
+
+    let benchmark_name = patch_cargo_toml(path, &bench_aoc_path, options.backend)?;
+    let inputs = patch_main_rs(path, &bench_aoc_path, &benchmark_name, options)?;
+    patch_modules(path, &bench_aoc_path)?;
+
+    // build the benchmark first, so build errors can be told apart from
+    // failing benchmarks
+    let status = options
+        .cargo_bench(&bench_aoc_path)
+        .arg("--no-run")
+        .status()?;
+    if !status.success() {
+        return Err(CargoError {
+            message: "Project does not build",
+            status,
+        }
+        .into());
+    }
+
+    match options.backend {
+        Backend::Divan => println!(
+            "--------- Running benchmark for at least {}s and no more than {}s ...",
+            options.min_time, options.max_time
+        ),
+        Backend::Criterion => {
+            println!(
+                "--------- Running benchmark with Criterion for {}s ...",
+                options.min_time
+            );
+            if options.sample_size.is_some() {
+                eprintln!("Warning: Criterion does not support --sample-size");
+            }
+        }
+    }
+
+    // let Criterion write its results to a known location
+    let criterion_home = target_path.canonicalize()?.join("criterion");
+
+    let mut process = options
+        .cargo_bench(&bench_aoc_path)
+        .env("CRITERION_HOME", &criterion_home)
+        .arg("--")
+        .args(options.backend_args())
+        .stdout(Stdio::piped())
         .spawn()?;
-    process.wait()?;
 
-    Ok(())
+    // forward the benchmark's output to the terminal and keep it for parsing
+    let mut stdout = String::new();
+    for line in BufReader::new(process.stdout.take().unwrap()).lines() {
+        let line = line?;
+        println!("{line}");
+        stdout.push_str(&line);
+        stdout.push('\n');
+    }
+    let status = process.wait()?;
+    if !status.success() {
+        // report wrong answers instead of the status of `cargo bench`
+        if let Some(line) = stdout.lines().find(|l| l.starts_with(WRONG_ANSWERS)) {
+            bail!("{line}");
+        }
+        return Err(CargoError {
+            message: "Benchmark failed",
+            status,
+        }
+        .into());
+    }
+
+    let timings = match options.backend {
+        Backend::Divan => {
+            let results = divan::parse_output(&stdout);
+            if inputs.is_empty() {
+                let (_, timings) = results
+                    .into_iter()
+                    .next()
+                    .context("Unable to find benchmark results in Divan's output")?;
+                vec![(None, timings)]
+            } else {
+                let results = results
+                    .into_iter()
+                    .filter(|(name, _)| inputs.contains(name))
+                    .map(|(name, timings)| (Some(name), timings))
+                    .collect::<Vec<_>>();
+                if results.is_empty() {
+                    bail!("Unable to find benchmark results in Divan's output");
+                }
+                results
+            }
+        }
+        Backend::Criterion => {
+            if inputs.is_empty() {
+                vec![(
+                    None,
+                    criterion::read_results(&criterion_home, &benchmark_name)?,
+                )]
+            } else {
+                let group_home = criterion_home.join(&benchmark_name);
+                inputs
+                    .into_iter()
+                    .map(|input| {
+                        let timings = criterion::read_results(&group_home, &input)?;
+                        Ok((Some(input), timings))
+                    })
+                    .collect::<Result<_>>()?
+            }
+        }
+    };
+
+    let memory = memory::parse_output(&stdout);
+    if options.memory && memory.is_empty() {
+        bail!("Unable to find the memory usage in the benchmark's output");
+    }
+    let parts = parts::parse_output(&stdout);
+
+    Ok(timings
+        .into_iter()
+        .map(|(input, timings)| {
+            let memory = memory.iter().find(|(i, _)| *i == input).map(|(_, m)| *m);
+            let parts = parts
+                .iter()
+                .filter(|(i, _, _)| *i == input)
+                .map(|(_, part, time)| (part.clone(), *time))
+                .collect();
+            BenchResult {
+                input,
+                timings,
+                memory,
+                parts,
+            }
+        })
+        .collect())
+}
+
+/// Find all projects in a directory (i.e. all subdirectories named `dayNN`
+/// that contain a `Cargo.toml` file)
+fn find_projects(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut result = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with("day"))
+            && fs::exists(path.join("Cargo.toml"))?
+        {
+            result.push(path);
+        }
+    }
+    result.sort();
+    Ok(result)
 }
 
-fn main() -> Result<()> {
+/// Expand a target given on the command line to a list of project paths. A
+/// target can be the path of a project, a directory containing projects (e.g.
+/// `2024`), or a glob pattern matching either of them (e.g. `2023/day2*`).
+fn expand_target(target: &str) -> Result<Vec<PathBuf>> {
+    let mut paths = glob::glob(target)?.collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() {
+        bail!("No project matches {target:?}");
+    }
+    paths.sort();
+
+    let mut result = Vec::new();
+    for path in paths {
+        if fs::exists(path.join("Cargo.toml"))? {
+            result.push(path);
+        } else if path.is_dir() {
+            let projects = find_projects(&path)?;
+            if projects.is_empty() {
+                bail!("Unable to find any projects in {path:?}");
+            }
+            result.extend(projects);
+        }
+    }
+    Ok(result)
+}
+
+/// Regenerate all tables marked with `bench-aoc` comments in the README.md at
+/// `root` from the latest records of the given `machine` (with the default
+/// input file). Warn about years with records but without a table.
+fn update_readme(root: &Path, history: &[Record], machine: &str) -> Result<()> {
+    let readme_path = root.join("README.md");
+    let readme = fs::read_to_string(&readme_path)?;
+
+    let projects = history
+        .iter()
+        .filter(|r| r.machine == machine)
+        .map(|r| r.project.as_str());
+    for year in readme::unmarked_years(&readme, projects) {
+        eprintln!(
+            "Warning: README.md has no table for {year}. Add `<!-- bench-aoc:begin {year} -->` \
+            and `<!-- bench-aoc:end -->` where it should be."
+        );
+    }
+
+    let mut tables = BTreeMap::new();
+    for year in readme::marked_years(&readme) {
+        let mut days = Vec::new();
+        let year_path = root.join(year.to_string());
+        if !fs::exists(&year_path)? {
+            continue;
+        }
+        for path in find_projects(&year_path)? {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let Ok(day) = name["day".len()..].parse::<u32>() else {
+                continue;
+            };
+            let project = format!("{year}/{name}");
+            let median =
+                history::find_latest_default(history, &project, machine).map(|r| r.median());
+            days.push((day, median));
+        }
+
+        if days.iter().all(|(_, median)| median.is_none()) {
+            eprintln!("Warning: No results for {year}. Keeping its table as it is.");
+            continue;
+        }
+        tables.insert(year, readme::render_table(year, &days));
+    }
+
+    write_file_if_necessary(&readme_path, &readme::update_tables(&readme, &tables)?)
+}
+
+fn main() -> Result<ExitCode> {
     let args = Args::parse();
-    let Command::BenchAoc { paths } = args.command;
+    let Command::BenchAoc {
+        paths,
+        years,
+        update_readme: should_update_readme,
+        results,
+        save_baseline,
+        compare,
+        threshold,
+        summary: summary_path,
+        options,
+    } = args.command;
+
+    let root = history::repository_root(Path::new(".")).map(PathBuf::from);
+    let results_path = results.unwrap_or_else(|| {
+        root.clone()
+            .unwrap_or_default()
+            .join("bench-aoc-results.jsonl")
+    });
+    let history = history::load(&results_path)?;
+    let machine = history::machine_name();
+
+    let mut targets = paths;
+    for year in years {
+        let root = root
+            .as_ref()
+            .context("Unable to find the repository root")?;
+        targets.push(root.join(year.to_string()).to_string_lossy().to_string());
+    }
+    let mut paths = Vec::new();
+    for target in targets {
+        paths.extend(expand_target(&target)?);
+    }
 
+    let mut records = Vec::new();
+    let mut skipped = Vec::new();
+    let mut exit_code = None;
     for path in paths {
-        bench(&path)?;
+        let results = match bench(&path, &options) {
+            Ok(results) => results,
+            Err(e) => {
+                // exit with the status of the first failed `cargo bench`
+                // command, clamped to a non-zero exit code
+                exit_code.get_or_insert_with(|| {
+                    e.downcast_ref::<CargoError>()
+                        .and_then(|e| e.status.code())
+                        .map_or(ExitCode::FAILURE, |c| ExitCode::from(c.clamp(1, 255) as u8))
+                });
+
+                eprintln!("--------- Skipping {path:?}: {e:#}");
+                skipped.push(Skipped {
+                    project: path.to_string_lossy().to_string(),
+                    reason: format!("{e:#}"),
+                });
+                continue;
+            }
+        };
+
+        // identify the project by its path relative to the repository root
+        let canonical = path.canonicalize()?;
+        let project = root
+            .as_ref()
+            .and_then(|r| canonical.strip_prefix(r).ok())
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+
+        let commit = history::commit_id(&path);
+        for result in results {
+            let mut record = Record::new(
+                project.clone(),
+                result.input,
+                commit.clone(),
+                machine.clone(),
+                save_baseline.clone(),
+                &result.timings,
+                result.memory,
+            );
+            record.parts_ns = result
+                .parts
+                .into_iter()
+                .map(|(part, time)| (part, time.as_nanos() as u64))
+                .collect();
+            records.push(record);
+        }
     }
 
-    Ok(())
+    history::append(&results_path, &records)?;
+
+    if records.len() + skipped.len() > 1 {
+        let summary = Summary::new(&records, skipped);
+        println!("--------- Summary");
+        print!("{}", summary.to_table());
+        if let Some(summary_path) = summary_path {
+            summary.write(&summary_path)?;
+        }
+    }
+
+    if should_update_readme {
+        let root = root
+            .as_ref()
+            .context("Unable to find the repository root")?;
+        let all_records = history.iter().chain(&records).cloned().collect::<Vec<_>>();
+        update_readme(root, &all_records, &machine)?;
+    }
+
+    if let Some(baseline) = compare {
+        let baseline = baseline.as_deref();
+        println!(
+            "--------- Comparison with {}",
+            baseline.unwrap_or("previous run")
+        );
+        let mut regressions = 0;
+        for r in &records {
+            let Some(before) = history::find_reference(
+                &history,
+                &r.project,
+                r.input.as_deref(),
+                &machine,
+                baseline,
+            ) else {
+                println!("{}: no previous results", r.name());
+                continue;
+            };
+            let c = Comparison { before, after: r };
+            println!("{}", c.describe(threshold));
+            if c.is_regression(threshold) {
+                regressions += 1;
+            }
+        }
+        if regressions > 0 {
+            bail!("{regressions} project(s) got slower by more than {threshold}%");
+        }
+    }
+
+    Ok(exit_code.unwrap_or(ExitCode::SUCCESS))
 }
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the lines in which the benchmark reports the memory usage of a
+/// solution, e.g. `Memory (input.txt): 12 allocations, 4096 bytes allocated,
+/// 2048 bytes peak`
+pub const OUTPUT_PREFIX: &str = "Memory";
+
+/// The memory usage of one run of a solution
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Memory {
+    /// Number of allocations (including reallocations)
+    pub allocations: u64,
+
+    /// Total number of bytes allocated
+    pub allocated_bytes: u64,
+
+    /// Maximum number of bytes allocated at the same time
+    pub peak_bytes: u64,
+}
+
+/// Format a number of bytes with a binary unit (e.g. `1.5 MiB`)
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let value = format!("{value:.1}");
+    let value = value.strip_suffix(".0").unwrap_or(&value);
+    format!("{value} {}", UNITS[unit])
+}
+
+/// Parse a line reported by the benchmark. Return the name of the input file
+/// (if any) and the memory usage.
+fn parse_line(line: &str) -> Option<(Option<String>, Memory)> {
+    let rest = line.trim().strip_prefix(OUTPUT_PREFIX)?;
+    let (input, rest) = rest.split_once(':')?;
+    let input = input.trim();
+    let input = match input.strip_prefix('(').and_then(|i| i.strip_suffix(')')) {
+        Some(input) => Some(input.to_string()),
+        None if input.is_empty() => None,
+        None => return None,
+    };
+
+    let mut numbers = rest
+        .split(',')
+        .map(|s| s.split_whitespace().next()?.parse::<u64>().ok());
+    let memory = Memory {
+        allocations: numbers.next()??,
+        allocated_bytes: numbers.next()??,
+        peak_bytes: numbers.next()??,
+    };
+    Some((input, memory))
+}
+
+/// Parse the memory usage the benchmark reported for each input file
+pub fn parse_output(stdout: &str) -> Vec<(Option<String>, Memory)> {
+    stdout.lines().filter_map(parse_line).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1 KiB");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3 GiB");
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let stdout = "Timer precision: 20 ns\n\
+            Memory: 12 allocations, 4096 bytes allocated, 2048 bytes peak\n\
+            Memory (large.txt): 1 allocations, 10 bytes allocated, 10 bytes peak\n\
+            Memory usage is great\n";
+        assert_eq!(
+            parse_output(stdout),
+            vec![
+                (
+                    None,
+                    Memory {
+                        allocations: 12,
+                        allocated_bytes: 4096,
+                        peak_bytes: 2048,
+                    }
+                ),
+                (
+                    Some("large.txt".to_string()),
+                    Memory {
+                        allocations: 1,
+                        allocated_bytes: 10,
+                        peak_bytes: 10,
+                    }
+                ),
+            ]
+        );
+    }
+}
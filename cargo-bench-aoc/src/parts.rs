@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+/// The parts of a solution that can be timed separately and the prefixes of
+/// the marker comments that start them (compared case-insensitively)
+pub const PARTS: [(&str, &str); 3] = [("parse", "parse"), ("part1", "part 1"), ("part2", "part 2")];
+
+/// Prefix of the lines in which the benchmark reports the median time of a
+/// part, e.g. `Part part1 (input.txt): 1234 ns`
+pub const OUTPUT_PREFIX: &str = "Part";
+
+/// Get the name of the part a comment line (e.g. `// part 1 - simple DFS`)
+/// starts. The prefix must be a whole word (followed by whitespace or the end
+/// of the line), so `// parsed` is not a marker. Doc comments are not
+/// considered.
+fn part_of_comment(line: &str) -> Option<&'static str> {
+    let comment = line.trim().strip_prefix("//")?;
+    if comment.starts_with('/') || comment.starts_with('!') {
+        return None;
+    }
+    let comment = comment.trim_start().to_lowercase();
+    PARTS
+        .iter()
+        .find(|(_, prefix)| {
+            comment
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        })
+        .map(|(name, _)| *name)
+}
+
+/// Get the indentation of a line
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Insert a call to `crate::__aoc_bench_part` before every marker comment
+/// (`// parse ...`, `// part 1 ...`, `// part 2 ...`) at the top level of the
+/// main function, i.e. indented like the first line of its body. Expects the
+/// body to end with a closing brace indented like `fn main(`. Return `None`
+/// if there are no marker comments.
+pub fn insert_markers(source: &str) -> Option<String> {
+    let mut result = String::new();
+    let mut main_indentation = None;
+    let mut body_indentation = None;
+    let mut found = false;
+    for line in source.split_inclusive('\n') {
+        let indentation = indentation(line);
+        if main_indentation.is_none()
+            && line.trim_start().starts_with("fn main(")
+            && !line.trim_end().ends_with('}')
+        {
+            main_indentation = Some(indentation);
+        } else if let Some(main) = main_indentation {
+            if indentation == main && line.trim_start().starts_with('}') {
+                main_indentation = None;
+                body_indentation = None;
+            } else if !line.trim().is_empty() {
+                let body = *body_indentation.get_or_insert(indentation);
+                if indentation == body
+                    && let Some(part) = part_of_comment(line)
+                {
+                    result.push_str(&format!("{body}crate::__aoc_bench_part({part:?});\n"));
+                    found = true;
+                }
+            }
+        }
+        result.push_str(line);
+    }
+    found.then_some(result)
+}
+
+/// Parse a line reported by the benchmark. Return the name of the input file
+/// (if any), the name of the part, and its median time.
+fn parse_line(line: &str) -> Option<(Option<String>, String, Duration)> {
+    let rest = line.trim().strip_prefix(OUTPUT_PREFIX)?.strip_prefix(' ')?;
+    let (name, rest) = rest.split_once(':')?;
+    let (part, input) = match name.split_once(' ') {
+        Some((part, input)) => {
+            let input = input.strip_prefix('(')?.strip_suffix(')')?;
+            (part, Some(input.to_string()))
+        }
+        None => (name, None),
+    };
+    if !PARTS.iter().any(|(p, _)| *p == part) {
+        return None;
+    }
+    let ns = rest.trim().strip_suffix("ns")?.trim().parse().ok()?;
+    Some((input, part.to_string(), Duration::from_nanos(ns)))
+}
+
+/// Parse the median times of the parts the benchmark reported
+pub fn parse_output(stdout: &str) -> Vec<(Option<String>, String, Duration)> {
+    stdout.lines().filter_map(parse_line).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_markers() {
+        let source = "// parse the input\n\
+            fn parse(s: &str) {\n    // parse numbers\n}\n\
+            fn main() {\n    let input = read();\n\n    // parse input\n    let x = parse(&input);\n\n    \
+            // Part 1 - simple DFS\n    for i in x {\n        // part 2 is below\n    }\n\n    \
+            /// part 2\n    // part 2\n    println!();\n}\n";
+        assert_eq!(
+            insert_markers(source).unwrap(),
+            "// parse the input\n\
+            fn parse(s: &str) {\n    // parse numbers\n}\n\
+            fn main() {\n    let input = read();\n\n    \
+            crate::__aoc_bench_part(\"parse\");\n    // parse input\n    let x = parse(&input);\n\n    \
+            crate::__aoc_bench_part(\"part1\");\n    // Part 1 - simple DFS\n    for i in x {\n        // part 2 is below\n    }\n\n    \
+            /// part 2\n    crate::__aoc_bench_part(\"part2\");\n    // part 2\n    println!();\n}\n"
+        );
+        assert_eq!(
+            insert_markers("fn main() {\n    // compute result\n}\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_insert_markers_indentation() {
+        // tabs and two spaces, e.g. in a module or with another rustfmt config
+        let source = "mod solution {\n\tfn main() {\n\t\t// parse\n\t\tlet x = 1;\n\t\tif x > 0 {\n\t\t\t// part 1\n\t\t}\n\t}\n\t// part 2\n}\n";
+        assert_eq!(
+            insert_markers(source).unwrap(),
+            "mod solution {\n\tfn main() {\n\t\tcrate::__aoc_bench_part(\"parse\");\n\t\t// parse\n\t\tlet x = 1;\n\t\tif x > 0 {\n\t\t\t// part 1\n\t\t}\n\t}\n\t// part 2\n}\n"
+        );
+        assert_eq!(
+            insert_markers("fn main() {\n  // part 1\n  let x = 1;\n}\n").unwrap(),
+            "fn main() {\n  crate::__aoc_bench_part(\"part1\");\n  // part 1\n  let x = 1;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_markers_whole_words() {
+        let source = "fn main() {\n    // parsed elsewhere\n    // part 10\n    // parse\n    // Part 2\tfast\n}\n";
+        assert_eq!(
+            insert_markers(source).unwrap(),
+            "fn main() {\n    // parsed elsewhere\n    // part 10\n    \
+            crate::__aoc_bench_part(\"parse\");\n    // parse\n    \
+            crate::__aoc_bench_part(\"part2\");\n    // Part 2\tfast\n}\n"
+        );
+        assert_eq!(insert_markers("fn main() {\n    // parsed\n}\n"), None);
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let stdout = "Part parse: 100 ns\n\
+            Part part1 (large.txt): 2000 ns\n\
+            Part unknown: 5 ns\n\
+            Partition: 17\n";
+        assert_eq!(
+            parse_output(stdout),
+            vec![
+                (None, "parse".to_string(), Duration::from_nanos(100)),
+                (
+                    Some("large.txt".to_string()),
+                    "part1".to_string(),
+                    Duration::from_nanos(2000)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::HashSet;
+
+use anyhow::{Result, bail};
+use syn::{
+    Attribute, Expr, File, Item, Macro, Stmt, Token, UseTree,
+    parse::Parser,
+    parse_quote,
+    punctuated::Punctuated,
+    visit::{self, Visit},
+    visit_mut::{self, VisitMut},
+};
+
+/// Macros that print to stdout
+const PRINT_MACROS: [&str; 2] = ["print", "println"];
+
+/// Macros that print to stderr
+const EPRINT_MACROS: [&str; 2] = ["eprint", "eprintln"];
+
+/// Macros that write to a given writer
+const WRITE_MACROS: [&str; 2] = ["write", "writeln"];
+
+/// Functions whose calls are replaced (see [patch_file])
+const PATCHED_FNS: [&[&str]; 5] = [
+    &["std", "fs", "read_to_string"],
+    &["std", "env", "args"],
+    &["input", "read_input"],
+    &["input", "read_input_or_exit"],
+    &["input", "input_path"],
+];
+
+/// An item imported with a `use` statement
+struct Import {
+    /// The name under which the item is available (`*` for glob imports)
+    name: String,
+
+    /// The full path of the item (or the module for glob imports)
+    path: Vec<String>,
+}
+
+/// Recursively collect all imports from a `use` tree
+fn collect_imports(tree: &UseTree, prefix: &mut Vec<String>, result: &mut Vec<Import>) {
+    match tree {
+        UseTree::Path(p) => {
+            prefix.push(p.ident.to_string());
+            collect_imports(&p.tree, prefix, result);
+            prefix.pop();
+        }
+        UseTree::Name(n) if n.ident == "self" => {
+            if let Some(last) = prefix.last() {
+                result.push(Import {
+                    name: last.clone(),
+                    path: prefix.clone(),
+                });
+            }
+        }
+        UseTree::Name(n) => {
+            let mut path = prefix.clone();
+            path.push(n.ident.to_string());
+            result.push(Import {
+                name: n.ident.to_string(),
+                path,
+            });
+        }
+        UseTree::Rename(r) => {
+            let mut path = prefix.clone();
+            path.push(r.ident.to_string());
+            result.push(Import {
+                name: r.rename.to_string(),
+                path,
+            });
+        }
+        UseTree::Glob(_) => result.push(Import {
+            name: "*".to_string(),
+            path: prefix.clone(),
+        }),
+        UseTree::Group(g) => {
+            for t in &g.items {
+                collect_imports(t, prefix, result);
+            }
+        }
+    }
+}
+
+/// Patches a syntax tree so it can be benchmarked
+struct Patcher {
+    imports: Vec<Import>,
+
+    /// Names of local variables that have been initialized with an
+    /// expression writing to stdout (e.g. `let mut out = stdout().lock()`)
+    stdout_vars: HashSet<String>,
+}
+
+impl Patcher {
+    fn new(file: &File) -> Self {
+        let mut imports = Vec::new();
+        for item in &file.items {
+            if let Item::Use(u) = item {
+                collect_imports(&u.tree, &mut Vec::new(), &mut imports);
+            }
+        }
+        Self {
+            imports,
+            stdout_vars: HashSet::new(),
+        }
+    }
+
+    /// Resolve a path (e.g. `fs::read_to_string`) to its full path (e.g.
+    /// `std::fs::read_to_string`) with the help of the collected imports
+    fn resolve(&self, path: &syn::Path) -> Vec<String> {
+        let segments = path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>();
+        if path.leading_colon.is_some() {
+            return segments;
+        }
+        if let Some(i) = self.imports.iter().find(|i| i.name == segments[0]) {
+            let mut result = i.path.clone();
+            result.extend_from_slice(&segments[1..]);
+            return result;
+        }
+        if segments.len() == 1 {
+            // the function might have been imported with a glob
+            for i in self.imports.iter().filter(|i| i.name == "*") {
+                let mut result = i.path.clone();
+                result.push(segments[0].clone());
+                if PATCHED_FNS.iter().any(|f| result == *f) {
+                    return result;
+                }
+            }
+        }
+        segments
+    }
+
+    fn is_fn(&self, func: &Expr, full_path: &[&str]) -> bool {
+        match func {
+            Expr::Path(p) => self.resolve(&p.path) == full_path,
+            _ => false,
+        }
+    }
+
+    /// Check if an expression writes to stdout (e.g. `stdout()`,
+    /// `io::stdout().lock()`, or a variable initialized with such an
+    /// expression)
+    fn writes_to_stdout(&self, expr: &Expr) -> bool {
+        struct StdoutFinder<'a> {
+            patcher: &'a Patcher,
+            found: bool,
+        }
+
+        impl Visit<'_> for StdoutFinder<'_> {
+            fn visit_expr_call(&mut self, c: &syn::ExprCall) {
+                if self.patcher.is_fn(&c.func, &["std", "io", "stdout"]) {
+                    self.found = true;
+                }
+                visit::visit_expr_call(self, c);
+            }
+
+            fn visit_expr_path(&mut self, p: &syn::ExprPath) {
+                if let Some(ident) = p.path.get_ident()
+                    && self.patcher.stdout_vars.contains(&ident.to_string())
+                {
+                    self.found = true;
+                }
+            }
+        }
+
+        let mut finder = StdoutFinder {
+            patcher: self,
+            found: false,
+        };
+        finder.visit_expr(expr);
+        finder.found
+    }
+
+    /// Replace a macro call that prints something with an expression that
+    /// formats the output but does not print it. Pass output to stdout (i.e.
+    /// the answers, also if it is written with `write!`) to
+    /// `__aoc_bench_print`, so it can be validated. Wrap output to stderr in
+    /// [std::hint::black_box] so it won't be optimized away. Return `None` if
+    /// the macro should not be replaced.
+    fn replace_macro(&self, mac: &Macro) -> Option<Expr> {
+        let name = mac.path.segments.last()?.ident.to_string();
+        if PRINT_MACROS.contains(&name.as_str()) {
+            let tokens = &mac.tokens;
+            let newline = name == "println";
+            if tokens.is_empty() {
+                return Some(parse_quote!(crate::__aoc_bench_print(String::new(), #newline)));
+            }
+            return Some(parse_quote!(crate::__aoc_bench_print(format!(#tokens), #newline)));
+        }
+
+        if EPRINT_MACROS.contains(&name.as_str()) {
+            let tokens = &mac.tokens;
+            if tokens.is_empty() {
+                return Some(parse_quote!({}));
+            }
+            return Some(parse_quote!({
+                let _ = std::hint::black_box(format!(#tokens));
+            }));
+        }
+
+        if WRITE_MACROS.contains(&name.as_str()) {
+            let args = mac
+                .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                .ok()?;
+            let mut args = args.into_iter();
+            let writer = args.next()?;
+            if self.writes_to_stdout(&writer) {
+                // keep the result type of writing to stdout
+                let newline = name == "writeln";
+                let output: Expr = if args.len() == 0 {
+                    parse_quote!(String::new())
+                } else {
+                    parse_quote!(format!(#(#args),*))
+                };
+                return Some(parse_quote!({
+                    crate::__aoc_bench_print(#output, #newline);
+                    Ok::<(), std::io::Error>(())
+                }));
+            }
+        }
+
+        None
+    }
+}
+
+impl VisitMut for Patcher {
+    fn visit_local_mut(&mut self, local: &mut syn::Local) {
+        if let Some(init) = &local.init
+            && let syn::Pat::Ident(p) = &local.pat
+            && self.writes_to_stdout(&init.expr)
+        {
+            self.stdout_vars.insert(p.ident.to_string());
+        }
+        visit_mut::visit_local_mut(self, local);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        if let Stmt::Macro(m) = stmt
+            && let Some(e) = self.replace_macro(&m.mac)
+        {
+            // blocks without a value do not need a trailing semicolon
+            let semi = match &e {
+                Expr::Block(b) if !matches!(b.block.stmts.last(), Some(Stmt::Expr(_, None))) => {
+                    None
+                }
+                _ => m.semi_token,
+            };
+            *stmt = Stmt::Expr(e, semi);
+            return;
+        }
+        visit_mut::visit_stmt_mut(self, stmt);
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+
+        match expr {
+            Expr::Macro(m) => {
+                if let Some(e) = self.replace_macro(&m.mac) {
+                    *expr = e;
+                }
+            }
+
+            Expr::Call(c) => {
+                if self.is_fn(&c.func, &["std", "fs", "read_to_string"]) {
+                    // read input files from memory instead
+                    *c.func = parse_quote!(crate::__aoc_bench_read_to_string);
+                } else if self.is_fn(&c.func, &["input", "read_input"]) {
+                    // the same for the shared input library, which reports
+                    // errors as `InputError`s
+                    let path = &c.args;
+                    *expr = parse_quote!({
+                        let path = #path;
+                        crate::__aoc_bench_read_to_string(&path).map_err(|source| {
+                            input::InputError::Io {
+                                path: std::path::Path::new(&path).to_path_buf(),
+                                source,
+                            }
+                        })
+                    });
+                } else if self.is_fn(&c.func, &["input", "read_input_or_exit"]) {
+                    let path = &c.args;
+                    *expr = parse_quote!(
+                        crate::__aoc_bench_read_to_string(#path).expect("Could not read input")
+                    );
+                } else if self.is_fn(&c.func, &["input", "input_path"]) {
+                    // the library reads the arguments itself
+                    let path = &c.args;
+                    *expr = parse_quote!(String::from(#path));
+                } else if self.is_fn(&c.func, &["std", "env", "args"]) {
+                    // hide the arguments passed to the benchmark, so they
+                    // will not be mistaken for the path of an input file
+                    let orig = expr.clone();
+                    *expr = parse_quote!(#orig.take(1));
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Patch the contents of a Rust source file: replace calls to
+/// `fs::read_to_string` and to the reading functions of the shared `input`
+/// library with `__aoc_bench_read_to_string`, replace print macros and
+/// writes to stdout with code that only formats the output, and hide
+/// command-line arguments from `env::args()`.
+fn patch_file(file: &mut File) {
+    let mut patcher = Patcher::new(file);
+    patcher.visit_file_mut(file);
+}
+
+/// Patch the contents of a module (i.e. any source file except `main.rs`).
+/// See [patch_file].
+pub fn patch_module(source: &str) -> Result<String> {
+    let mut file = syn::parse_file(source)?;
+    patch_file(&mut file);
+    Ok(prettyplease::unparse(&file))
+}
+
+/// Patch the contents of `main.rs` (see [patch_file]) and rename the `main`
+/// function to `__bench_aoc_main`, so it can be called by a benchmark. If
+/// given, add `attribute` to the function (e.g. `#[divan::bench]`).
+pub fn patch_main(source: &str, attribute: Option<&str>) -> Result<String> {
+    let mut file = syn::parse_file(source)?;
+    patch_file(&mut file);
+
+    let Some(main_fn) = file.items.iter_mut().find_map(|i| match i {
+        Item::Fn(f) if f.sig.ident == "main" => Some(f),
+        _ => None,
+    }) else {
+        bail!("Unable to find main function in main.rs");
+    };
+    main_fn.sig.ident = parse_quote!(__bench_aoc_main);
+    if let Some(attribute) = attribute {
+        main_fn
+            .attrs
+            .extend(Attribute::parse_outer.parse_str(attribute)?);
+    }
+
+    // imports such as `std::fs` might not be needed anymore
+    file.attrs
+        .extend(Attribute::parse_inner.parse_str("#![allow(unused_imports)]")?);
+
+    Ok(prettyplease::unparse(&file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Normalize the formatting of a code snippet so it can be compared
+    fn normalize(code: &str) -> String {
+        prettyplease::unparse(&syn::parse_file(code).unwrap())
+    }
+
+    #[test]
+    fn test_main_with_result() {
+        let patched = patch_main(
+            r#"
+            use std::error::Error;
+            fn main() -> Result<(), Box<dyn Error>> {
+                println!("{}", 1);
+                Ok(())
+            }
+            "#,
+            Some("#[divan::bench(name = \"day13\")]"),
+        )
+        .unwrap();
+        assert_eq!(
+            patched,
+            normalize(
+                r#"
+                #![allow(unused_imports)]
+                use std::error::Error;
+                #[divan::bench(name = "day13")]
+                fn __bench_aoc_main() -> Result<(), Box<dyn Error>> {
+                    crate::__aoc_bench_print(format!("{}", 1), true);
+                    Ok(())
+                }
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn test_read_input() {
+        let patched = patch_module(
+            r#"
+            use input::*;
+            use std::fs::read_to_string;
+            use std::io;
+            fn a() {
+                let a = read_to_string("input.txt").unwrap();
+                let b = std::fs::read_to_string("input.txt").unwrap();
+                let c = io::read_to_string(io::stdin()).unwrap();
+                let d = std::env::args().nth(1);
+                let e = input::read_input_or_exit("input.txt");
+                let f = read_input(input_path("input.txt"))?;
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            patched,
+            normalize(
+                r#"
+                use input::*;
+                use std::fs::read_to_string;
+                use std::io;
+                fn a() {
+                    let a = crate::__aoc_bench_read_to_string("input.txt").unwrap();
+                    let b = crate::__aoc_bench_read_to_string("input.txt").unwrap();
+                    let c = io::read_to_string(io::stdin()).unwrap();
+                    let d = std::env::args().take(1).nth(1);
+                    let e = crate::__aoc_bench_read_to_string("input.txt")
+                        .expect("Could not read input");
+                    let f = {
+                        let path = String::from("input.txt");
+                        crate::__aoc_bench_read_to_string(&path).map_err(|source| {
+                            input::InputError::Io {
+                                path: std::path::Path::new(&path).to_path_buf(),
+                                source,
+                            }
+                        })
+                    }?;
+                }
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn test_print_macros() {
+        let patched = patch_module(
+            r#"
+            use std::io::{stdout, Write};
+            fn a(x: i32) {
+                // println!("in a comment (");
+                let s = match x {
+                    0 => print!(")"),
+                    _ => eprintln!("{x} )"),
+                };
+                println!();
+                eprintln!();
+                writeln!(stdout(), "{}", x).unwrap();
+                let mut out = stdout().lock();
+                write!(out, "{x}").unwrap();
+                writeln!(out)?;
+                write!(out, "{x}");
+                let mut v = Vec::new();
+                write!(v, "{x}").unwrap();
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            patched,
+            normalize(
+                r#"
+                use std::io::{stdout, Write};
+                fn a(x: i32) {
+                    let s = match x {
+                        0 => crate::__aoc_bench_print(format!(")"), false),
+                        _ => {
+                            let _ = std::hint::black_box(format!("{x} )"));
+                        }
+                    };
+                    crate::__aoc_bench_print(String::new(), true);
+                    {}
+                    {
+                        crate::__aoc_bench_print(format!("{}", x), true);
+                        Ok::<(), std::io::Error>(())
+                    }
+                    .unwrap();
+                    let mut out = stdout().lock();
+                    {
+                        crate::__aoc_bench_print(format!("{x}"), false);
+                        Ok::<(), std::io::Error>(())
+                    }
+                    .unwrap();
+                    {
+                        crate::__aoc_bench_print(String::new(), true);
+                        Ok::<(), std::io::Error>(())
+                    }?;
+                    {
+                        crate::__aoc_bench_print(format!("{x}"), false);
+                        Ok::<(), std::io::Error>(())
+                    };
+                    let mut v = Vec::new();
+                    write!(v, "{x}").unwrap();
+                }
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn test_missing_main() {
+        assert!(patch_main("fn not_main() {}", None).is_err());
+    }
+}
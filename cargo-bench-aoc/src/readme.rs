@@ -0,0 +1,161 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use anyhow::{Result, bail};
+
+use crate::divan::format_duration;
+
+/// Number of days per table row
+const DAYS_PER_ROW: usize = 5;
+
+/// Comment that marks the beginning of a generated table. Followed by the
+/// year, e.g. `<!-- bench-aoc:begin 2018 -->`.
+const BEGIN_MARKER: &str = "<!-- bench-aoc:begin ";
+
+/// Comment that marks the end of a generated table
+const END_MARKER: &str = "<!-- bench-aoc:end -->";
+
+/// Render the benchmark table of a year. `days` contains the number of each
+/// day and its median time (or `None` if there is no result for it).
+pub fn render_table(year: u32, days: &[(u32, Option<Duration>)]) -> String {
+    let mut result = String::from("<table>\n");
+    for row in days.chunks(DAYS_PER_ROW) {
+        result.push_str("<tr>\n");
+        for (day, time) in row {
+            let time = time.map_or("...".to_string(), |t| format_duration(t, 0));
+            result.push_str(&format!(
+                "<td><strong><a href=\"./{year}/day{day:02}/src/main.rs\">Day {day}:</a></strong></td><td align=\"right\">{time}</td>\n"
+            ));
+        }
+        result.push_str("</tr>\n");
+    }
+
+    let total = days.iter().filter_map(|(_, t)| *t).sum::<Duration>();
+    result.push_str(&format!(
+        "<tr>\n<td colspan=\"{}\" align=\"right\"><strong>Total:</strong></td><td align=\"right\"><strong>{}</strong></td>\n</tr>\n",
+        DAYS_PER_ROW * 2 - 1,
+        format_duration(total, 0)
+    ));
+
+    result.push_str("</table>\n");
+    result
+}
+
+/// Find the years of all tables marked with [BEGIN_MARKER] in a README
+pub fn marked_years(readme: &str) -> Vec<u32> {
+    readme
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix(BEGIN_MARKER))
+        .filter_map(|l| l.strip_suffix("-->"))
+        .filter_map(|y| y.trim().parse().ok())
+        .collect()
+}
+
+/// Find the years of the given projects (e.g. `2025/day01`) that have no
+/// marked table in a README
+pub fn unmarked_years<'a>(readme: &str, projects: impl Iterator<Item = &'a str>) -> BTreeSet<u32> {
+    let marked = marked_years(readme);
+    projects
+        .filter_map(|p| p.split_once('/')?.0.parse().ok())
+        .filter(|year| !marked.contains(year))
+        .collect()
+}
+
+/// Replace the contents between the begin and end markers of each year in
+/// `tables` with the corresponding table. Leave everything else untouched.
+pub fn update_tables(readme: &str, tables: &BTreeMap<u32, String>) -> Result<String> {
+    let mut result = String::new();
+    let mut lines = readme.split_inclusive('\n');
+    while let Some(line) = lines.next() {
+        result.push_str(line);
+
+        let Some(year) = line
+            .trim()
+            .strip_prefix(BEGIN_MARKER)
+            .and_then(|l| l.strip_suffix("-->"))
+            .and_then(|y| y.trim().parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        let mut old_table = String::new();
+        let mut end = None;
+        for line in lines.by_ref() {
+            if line.trim() == END_MARKER {
+                end = Some(line);
+                break;
+            }
+            old_table.push_str(line);
+        }
+        let Some(end) = end else {
+            bail!("Missing {END_MARKER} for the table of {year}");
+        };
+
+        match tables.get(&year) {
+            Some(table) => result.push_str(table),
+            None => result.push_str(&old_table),
+        }
+        result.push_str(end);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_table() {
+        let days = (1..=6)
+            .map(|d| (d, (d != 3).then(|| Duration::from_micros(d as u64 * 500))))
+            .collect::<Vec<_>>();
+        let table = render_table(2018, &days);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 15);
+        assert_eq!(
+            lines[2],
+            "<td><strong><a href=\"./2018/day01/src/main.rs\">Day 1:</a></strong></td><td align=\"right\">500µs</td>"
+        );
+        assert!(lines[4].ends_with("<td align=\"right\">...</td>"));
+        assert!(lines[9].contains("Day 6:"));
+        assert!(lines[12].ends_with("<strong>9ms</strong></td>"));
+    }
+
+    #[test]
+    fn test_update_tables() {
+        let readme = "# Title\n\n\
+            <!-- bench-aoc:begin 2018 -->\n<table>old</table>\n<!-- bench-aoc:end -->\n\n\
+            <!-- bench-aoc:begin 2017 -->\n<table>old</table>\n<!-- bench-aoc:end -->\n\
+            ## License\n";
+        assert_eq!(marked_years(readme), vec![2018, 2017]);
+
+        let tables = BTreeMap::from([(2018, "<table>new</table>\n".to_string())]);
+        assert_eq!(
+            update_tables(readme, &tables).unwrap(),
+            "# Title\n\n\
+            <!-- bench-aoc:begin 2018 -->\n<table>new</table>\n<!-- bench-aoc:end -->\n\n\
+            <!-- bench-aoc:begin 2017 -->\n<table>old</table>\n<!-- bench-aoc:end -->\n\
+            ## License\n"
+        );
+
+        assert!(update_tables("<!-- bench-aoc:begin 2018 -->\n<table>\n", &tables).is_err());
+    }
+
+    #[test]
+    fn test_unmarked_years() {
+        let readme = "<!-- bench-aoc:begin 2018 -->\n<table>old</table>\n<!-- bench-aoc:end -->\n";
+        let projects = [
+            "2018/day01",
+            "2025/day01",
+            "2019/day03",
+            "2025/day02",
+            "misc",
+        ];
+        assert_eq!(
+            unmarked_years(readme, projects.into_iter()),
+            BTreeSet::from([2019, 2025])
+        );
+    }
+}
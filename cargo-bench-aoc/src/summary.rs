@@ -0,0 +1,311 @@
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::{
+    divan::format_duration,
+    history::Record,
+    memory::{Memory, format_bytes},
+    parts::PARTS,
+};
+
+/// A project that could not be benchmarked
+#[derive(Serialize)]
+pub struct Skipped {
+    pub project: String,
+    pub reason: String,
+}
+
+/// The results of benchmarking several projects
+#[derive(Serialize)]
+pub struct Summary {
+    /// Records of all benchmarked projects, slowest first
+    pub projects: Vec<Record>,
+
+    /// Total median time per year (in nanoseconds). A project that has been
+    /// benchmarked with several inputs (see `--all-inputs`) only counts with
+    /// `input.txt` (or its first input if it has none).
+    pub years: BTreeMap<String, u64>,
+
+    pub skipped: Vec<Skipped>,
+}
+
+/// Capitalize the first letter of a string (e.g. `part 1` -> `Part 1`)
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map_or(String::new(), |c| c.to_uppercase().chain(chars).collect())
+}
+
+/// Get the year of a project (i.e. the first component of its path)
+fn year_of(project: &str) -> &str {
+    project.split(['/', '\\']).next().unwrap_or(project)
+}
+
+impl Summary {
+    pub fn new(records: &[Record], skipped: Vec<Skipped>) -> Self {
+        let mut projects = records.to_vec();
+        projects.sort_by_key(|r| std::cmp::Reverse(r.median_ns));
+
+        // pick one record per project, preferably the one of the default
+        // input
+        let rank = |r: &Record| {
+            (
+                r.input.as_deref().is_some_and(|i| i != "input.txt"),
+                r.input.clone(),
+            )
+        };
+        let mut counted = BTreeMap::<&str, &Record>::new();
+        for r in records {
+            counted
+                .entry(&r.project)
+                .and_modify(|c| {
+                    if rank(r) < rank(c) {
+                        *c = r;
+                    }
+                })
+                .or_insert(r);
+        }
+        let mut years = BTreeMap::new();
+        for r in counted.values() {
+            *years.entry(year_of(&r.project).to_string()).or_default() += r.median_ns;
+        }
+
+        Self {
+            projects,
+            years,
+            skipped,
+        }
+    }
+
+    /// Render the summary as a table that can be printed to the terminal
+    pub fn to_table(&self) -> String {
+        let width = self
+            .projects
+            .iter()
+            .map(|r| r.name().chars().count())
+            .chain(self.years.keys().map(|y| y.len() + "Total ".len()))
+            .chain(["Project".len()])
+            .max()
+            .unwrap();
+
+        // only show columns of parts that have been timed and memory columns
+        // if memory usage has been measured
+        let parts = PARTS
+            .iter()
+            .filter(|(p, _)| self.projects.iter().any(|r| r.parts_ns.contains_key(*p)))
+            .collect::<Vec<_>>();
+        let with_memory = self.projects.iter().any(|r| r.memory.is_some());
+
+        let mut result = format!("{:<width$}  {:>10}  {:>10}", "Project", "Median", "Mean");
+        for (_, label) in &parts {
+            result.push_str(&format!("  {:>10}", capitalize(label)));
+        }
+        if with_memory {
+            result.push_str(&format!("  {:>12}  {:>10}", "Allocations", "Peak"));
+        }
+        result.push('\n');
+        for r in &self.projects {
+            result.push_str(&format!(
+                "{:<width$}  {:>10}  {:>10}",
+                r.name(),
+                format_duration(Duration::from_nanos(r.median_ns), 1),
+                format_duration(Duration::from_nanos(r.mean_ns), 1),
+            ));
+            for (part, _) in &parts {
+                let time = r.parts_ns.get(*part).map_or("-".to_string(), |ns| {
+                    format_duration(Duration::from_nanos(*ns), 1)
+                });
+                result.push_str(&format!("  {time:>10}"));
+            }
+            if with_memory {
+                let (allocations, peak) =
+                    r.memory.map_or(("-".to_string(), "-".to_string()), |m| {
+                        (m.allocations.to_string(), format_bytes(m.peak_bytes))
+                    });
+                result.push_str(&format!("  {allocations:>12}  {peak:>10}"));
+            }
+            result.push('\n');
+        }
+        for (year, total) in &self.years {
+            result.push_str(&format!(
+                "{:<width$}  {:>10}\n",
+                format!("Total {year}"),
+                format_duration(Duration::from_nanos(*total), 1),
+            ));
+        }
+        for s in &self.skipped {
+            result.push_str(&format!("Skipped {}: {}\n", s.project, s.reason));
+        }
+        result
+    }
+
+    /// Render the summary as CSV (one line per project)
+    pub fn to_csv(&self) -> String {
+        let mut result = String::from(
+            "project,input,median_ns,mean_ns,fastest_ns,slowest_ns,parse_ns,part1_ns,part2_ns,allocations,allocated_bytes,peak_bytes\n",
+        );
+        for r in &self.projects {
+            let parts = PARTS
+                .iter()
+                .map(|(p, _)| {
+                    r.parts_ns
+                        .get(*p)
+                        .map_or(String::new(), |ns| ns.to_string())
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            let memory = r.memory.map_or(",,".to_string(), |m: Memory| {
+                format!("{},{},{}", m.allocations, m.allocated_bytes, m.peak_bytes)
+            });
+            result.push_str(&format!(
+                "{},{},{},{},{},{},{parts},{memory}\n",
+                r.project,
+                r.input.as_deref().unwrap_or(""),
+                r.median_ns,
+                r.mean_ns,
+                r.fastest_ns,
+                r.slowest_ns
+            ));
+        }
+        result
+    }
+
+    /// Write the summary to a file. The format (CSV or JSON) depends on the
+    /// extension of the file.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let contents = match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => self.to_csv(),
+            Some("json") => serde_json::to_string_pretty(self)? + "\n",
+            _ => bail!("Unsupported summary format {path:?}. Use a .csv or .json file."),
+        };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(project: &str, median_ns: u64) -> Record {
+        Record {
+            project: project.to_string(),
+            input: None,
+            commit: "abc1234".to_string(),
+            machine: "m".to_string(),
+            baseline: None,
+            timestamp: 0,
+            fastest_ns: median_ns,
+            slowest_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            memory: None,
+            parts_ns: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::new(
+            &[
+                record("2024/day01", 1_000),
+                record("2024/day02", 2_000_000),
+                record("2023/day25", 500),
+            ],
+            vec![Skipped {
+                project: "2024/day03".to_string(),
+                reason: "build failed".to_string(),
+            }],
+        );
+        let table = summary.to_table();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "Project         Median        Mean");
+        assert_eq!(lines[1], "2024/day02         2ms         2ms");
+        assert_eq!(lines[3], "2023/day25       500ns       500ns");
+        assert_eq!(lines[4], "Total 2023       500ns");
+        assert_eq!(lines[5], "Total 2024         2ms");
+        assert_eq!(lines[6], "Skipped 2024/day03: build failed");
+        assert_eq!(
+            summary.to_csv().lines().nth(1),
+            Some("2024/day02,,2000000,2000000,2000000,2000000,,,,,,")
+        );
+    }
+
+    #[test]
+    fn test_summary_with_all_inputs() {
+        let with_input = |project, input: &str, median_ns| Record {
+            input: Some(input.to_string()),
+            ..record(project, median_ns)
+        };
+        let summary = Summary::new(
+            &[
+                with_input("2024/day01", "large.txt", 5_000),
+                with_input("2024/day01", "input.txt", 1_000),
+                with_input("2024/day02", "b.txt", 3_000),
+                with_input("2024/day02", "a.txt", 2_000),
+                record("2024/day03", 500),
+            ],
+            Vec::new(),
+        );
+        assert_eq!(summary.projects.len(), 5);
+        assert_eq!(summary.years["2024"], 3_500);
+    }
+
+    #[test]
+    fn test_summary_with_parts() {
+        let mut with_parts = record("2025/day10", 3_000);
+        with_parts.parts_ns =
+            BTreeMap::from([("part1".to_string(), 1_000), ("part2".to_string(), 2_000)]);
+        let summary = Summary::new(&[with_parts, record("2025/day11", 500)], Vec::new());
+        let table = summary.to_table();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Project         Median        Mean      Part 1      Part 2"
+        );
+        assert_eq!(
+            lines[1],
+            "2025/day10         3µs         3µs         1µs         2µs"
+        );
+        assert_eq!(
+            lines[2],
+            "2025/day11       500ns       500ns           -           -"
+        );
+        assert_eq!(
+            summary.to_csv().lines().nth(1),
+            Some("2025/day10,,3000,3000,3000,3000,,1000,2000,,,")
+        );
+    }
+
+    #[test]
+    fn test_summary_with_memory() {
+        let mut with_memory = record("2024/day01", 1_000);
+        with_memory.memory = Some(Memory {
+            allocations: 42,
+            allocated_bytes: 4096,
+            peak_bytes: 3072,
+        });
+        let summary = Summary::new(&[with_memory, record("2024/day02", 500)], Vec::new());
+        let table = summary.to_table();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "Project         Median        Mean   Allocations        Peak"
+        );
+        assert_eq!(
+            lines[1],
+            "2024/day01         1µs         1µs            42       3 KiB"
+        );
+        assert_eq!(
+            lines[2],
+            "2024/day02       500ns       500ns             -           -"
+        );
+        assert_eq!(
+            summary.to_csv().lines().nth(1),
+            Some("2024/day01,,1000,1000,1000,1000,,,,42,4096,3072")
+        );
+    }
+}
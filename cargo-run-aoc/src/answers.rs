@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Characters that may appear in ASCII-art answers
+const ART_CHARS: [char; 4] = ['█', '#', '.', ' '];
+
+/// Characters that represent a lit pixel in ASCII-art answers
+const ART_PIXELS: [char; 2] = ['█', '#'];
+
+/// The answer to one part of a puzzle
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Record {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: usize,
+
+    /// The answer as text. For ASCII-art answers, this is the text
+    /// recognized by [ocr::recognize_str] or `None` if it could not be
+    /// recognized.
+    pub answer: Option<String>,
+
+    /// The raw lines of an ASCII-art answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub art: Option<Vec<String>>,
+
+    /// The time it took to calculate the answer (in nanoseconds)
+    pub elapsed_ns: u64,
+}
+
+/// An answer printed by a solution
+#[derive(Debug, PartialEq)]
+pub enum Answer {
+    Text(String),
+    Art(Vec<String>),
+}
+
+/// Check if a line could be part of an ASCII-art answer
+fn is_art_line(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| ART_CHARS.contains(&c))
+}
+
+/// Check if a line of an ASCII-art answer contains at least one lit pixel
+fn has_pixels(line: &str) -> bool {
+    line.contains(ART_PIXELS)
+}
+
+/// Split the output of a solution into answers. Every line is an answer
+/// except for consecutive lines that look like ASCII art, which are combined
+/// into a single answer.
+pub fn split_answers(stdout: &str) -> Vec<Answer> {
+    let lines = stdout.lines().collect::<Vec<_>>();
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if has_pixels(lines[i]) && is_art_line(lines[i]) {
+            let mut j = i + 1;
+            while j < lines.len() && is_art_line(lines[j]) {
+                j += 1;
+            }
+            if j - i > 1 {
+                // drop trailing lines without pixels
+                while !has_pixels(lines[j - 1]) {
+                    j -= 1;
+                }
+                result.push(Answer::Art(
+                    lines[i..j].iter().map(|l| l.to_string()).collect(),
+                ));
+                i = j;
+                continue;
+            }
+        }
+        if !lines[i].is_empty() {
+            result.push(Answer::Text(lines[i].to_string()));
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Convert the output of a solution to records. If the solution already
+/// printed JSON records (see `template/src/output.rs`), parse and return
+/// them. Otherwise, split the output into answers (see [split_answers]).
+pub fn parse_output(
+    stdout: &str,
+    year: Option<u32>,
+    day: Option<u32>,
+    elapsed: Duration,
+) -> Result<Vec<Record>> {
+    if !stdout.is_empty() && stdout.lines().all(|l| l.starts_with('{')) {
+        let mut result = Vec::new();
+        for l in stdout.lines() {
+            let mut r: Record = serde_json::from_str(l)?;
+            r.year = r.year.or(year);
+            r.day = r.day.or(day);
+            result.push(r);
+        }
+        return Ok(result);
+    }
+
+    let mut result = Vec::new();
+    let mut answers = split_answers(stdout).into_iter().peekable();
+    while let Some(a) = answers.next() {
+        let (answer, art) = match a {
+            Answer::Text(t) => (Some(t), None),
+            Answer::Art(lines) => {
+                let text = ocr::recognize_str(&lines.join("\n")).ok();
+
+                // solutions may print the recognized text below the art
+                if text.is_some() && answers.peek() == text.clone().map(Answer::Text).as_ref() {
+                    answers.next();
+                }
+
+                (text, Some(lines))
+            }
+        };
+        result.push(Record {
+            year,
+            day,
+            part: result.len() + 1,
+            answer,
+            art,
+            elapsed_ns: elapsed.as_nanos() as u64,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_answers() {
+        assert_eq!(
+            split_answers("42\nabc\n"),
+            vec![Answer::Text("42".into()), Answer::Text("abc".into())]
+        );
+
+        let stdout = "123\n\
+            █  █ ███ \n\
+            █  █ █  █\n\
+            ████ ███ \n\
+            █  █ █  █\n\
+            █  █ ███ \n";
+        let answers = split_answers(stdout);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0], Answer::Text("123".into()));
+        let Answer::Art(art) = &answers[1] else {
+            panic!("Expected ASCII art");
+        };
+        assert_eq!(art.len(), 5);
+
+        // a single line of pixels is not ASCII art
+        assert_eq!(split_answers("#\n5\n").len(), 2);
+    }
+
+    #[test]
+    fn test_parse_output_with_art() {
+        let stdout = "5\n\
+            #..#.###.\n\
+            #..#..#..\n\
+            ####..#..\n\
+            #..#..#..\n\
+            #..#..#..\n\
+            #..#.###.\n\
+            HI\n";
+        let records = parse_output(stdout, None, None, Duration::ZERO).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].part, 2);
+        assert_eq!(records[1].answer.as_deref(), Some("HI"));
+        assert_eq!(records[1].art.as_ref().map(|a| a.len()), Some(6));
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let records = parse_output(
+            "{\"year\":null,\"day\":null,\"part\":1,\"answer\":\"7\",\"elapsed_ns\":5}\n",
+            Some(2024),
+            Some(17),
+            Duration::from_nanos(10),
+        )
+        .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].year, Some(2024));
+        assert_eq!(records[0].elapsed_ns, 5);
+
+        let records =
+            parse_output("1\n2\n", Some(2015), Some(1), Duration::from_nanos(10)).unwrap();
+        assert_eq!(records[1].part, 2);
+        assert_eq!(records[1].answer.as_deref(), Some("2"));
+        assert_eq!(records[1].elapsed_ns, 10);
+    }
+}
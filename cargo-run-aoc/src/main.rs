@@ -0,0 +1,151 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Instant,
+};
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand};
+use toml_edit::DocumentMut;
+
+use crate::answers::{Record, parse_output};
+
+mod answers;
+
+#[derive(Parser)]
+#[command(author, version, about, bin_name = "cargo")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    RunAoc {
+        /// Paths of the projects to run
+        #[arg(required = true)]
+        paths: Vec<String>,
+
+        /// Input file to pass to the solutions (default: `input.txt` in each
+        /// project directory). Use `-` to read from stdin.
+        #[arg(long)]
+        input: Option<String>,
+
+        /// Print one JSON record per answer instead of plain text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Get the year and day of a puzzle from the path of its project directory
+/// (e.g. `2024/day17`)
+fn puzzle_id(path: &Path) -> (Option<u32>, Option<u32>) {
+    let day = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.strip_prefix("day"))
+        .and_then(|n| n.parse().ok());
+    let year = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.parse().ok());
+    (year, day)
+}
+
+/// Read the name of the project's binary from its `Cargo.toml` file
+fn binary_name(path: &Path) -> Result<String> {
+    let cargo_toml = fs::read_to_string(path.join("Cargo.toml"))?;
+    let document = cargo_toml.parse::<DocumentMut>()?;
+    Ok(document["package"]["name"]
+        .as_str()
+        .context("Project does not have a name")?
+        .to_string())
+}
+
+/// Build and run the project at the given `path` and return its answers
+fn run(path: &str, input: Option<&str>) -> Result<Vec<Record>> {
+    let path = PathBuf::from(path).canonicalize()?;
+
+    // look for Cargo.toml
+    if !fs::exists(path.join("Cargo.toml"))? {
+        bail!("Unable to find Cargo.toml in path {path:?}");
+    }
+
+    let status = std::process::Command::new("cargo")
+        .args(["build", "--release", "--quiet"])
+        .current_dir(&path)
+        .status()?;
+    if !status.success() {
+        bail!("Unable to build project {path:?}");
+    }
+
+    // make input path absolute because the solution runs in its project
+    // directory
+    let input = match input {
+        Some("-") => Some("-".to_string()),
+        Some(input) => Some(
+            Path::new(input)
+                .canonicalize()
+                .with_context(|| format!("Unable to find input file {input:?}"))?
+                .to_string_lossy()
+                .to_string(),
+        ),
+        None => None,
+    };
+
+    let binary = path
+        .join("target")
+        .join("release")
+        .join(binary_name(&path)?);
+    let start = Instant::now();
+    let output = std::process::Command::new(binary)
+        .args(input)
+        .env("AOC_OUTPUT", "json")
+        .current_dir(&path)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    let elapsed = start.elapsed();
+    if !output.status.success() {
+        bail!("Solution {path:?} failed with {}", output.status);
+    }
+
+    let (year, day) = puzzle_id(&path);
+    parse_output(&String::from_utf8(output.stdout)?, year, day, elapsed)
+}
+
+/// Print a record in a human-readable way
+fn print_record(r: &Record) {
+    let year = r.year.map(|y| y.to_string()).unwrap_or("?".to_string());
+    let day = r.day.map(|d| d.to_string()).unwrap_or("?".to_string());
+    println!(
+        "{year} day {day} part {}: {} ({:.2?})",
+        r.part,
+        r.answer.as_deref().unwrap_or("(see below)"),
+        std::time::Duration::from_nanos(r.elapsed_ns)
+    );
+    if let Some(art) = &r.art {
+        for l in art {
+            println!("{l}");
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let Command::RunAoc { paths, input, json } = args.command;
+
+    for path in paths {
+        for r in run(&path, input.as_deref())? {
+            if json {
+                println!("{}", serde_json::to_string(&r)?);
+            } else {
+                print_record(&r);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use std::{
+    env,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::parse::ParseError;
+
+pub mod parse;
+
+/// Texts that the Advent of Code website returns instead of a puzzle input
+/// (e.g. if `aoc-load` was called with an expired cookie or for a puzzle that
+/// does not exist)
+const ERROR_PAGES: [(&str, &str); 3] = [
+    (
+        "Puzzle inputs differ by user",
+        "not logged in (is the session cookie valid?)",
+    ),
+    (
+        "Please don't repeatedly request this endpoint before it unlocks",
+        "the puzzle has not been unlocked yet",
+    ),
+    ("404 Not Found", "there is no puzzle for this year and day"),
+];
+
+#[derive(Debug)]
+pub enum InputError {
+    /// The input file does not exist
+    Missing { path: PathBuf },
+
+    /// The input file exists but could not be read
+    Io { path: PathBuf, source: io::Error },
+
+    /// The input file contains an error message from the Advent of Code
+    /// website instead of a puzzle input
+    NotAnInput { path: PathBuf, reason: &'static str },
+
+    /// The input file starts with a header (see [read_input]) that names
+    /// another puzzle than the one being solved. Puzzles are given as
+    /// `(year, day)`.
+    WrongPuzzle {
+        path: PathBuf,
+        expected: (u32, u32),
+        found: (u32, u32),
+    },
+
+    /// The input file is empty
+    Empty { path: PathBuf },
+
+    /// A line ends with whitespace although the puzzle does not allow it
+    TrailingWhitespace { line: usize },
+
+    /// The input could not be parsed
+    Parse(ParseError),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            InputError::Missing { path } => write!(f, "input file {path:?} does not exist"),
+            InputError::Io { path, source } => {
+                write!(f, "could not read input file {path:?}: {source}")
+            }
+            InputError::NotAnInput { path, reason } => {
+                write!(f, "{path:?} is not a puzzle input: {reason}")
+            }
+            InputError::WrongPuzzle {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{path:?} is the input of {} day {}, not of {} day {}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            InputError::Empty { path } => write!(f, "input file {path:?} is empty"),
+            InputError::TrailingWhitespace { line } => {
+                write!(f, "line {line}: unexpected trailing whitespace")
+            }
+            InputError::Parse(e) => write!(f, "could not parse input: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            InputError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for InputError {
+    fn from(e: ParseError) -> Self {
+        InputError::Parse(e)
+    }
+}
+
+/// Get the year and day of a puzzle from the path of its project directory
+/// (e.g. `2024/day17`)
+fn puzzle_id(path: &Path) -> Option<(u32, u32)> {
+    let day = path
+        .file_name()?
+        .to_str()?
+        .strip_prefix("day")?
+        .parse()
+        .ok()?;
+    let year = path.parent()?.file_name()?.to_str()?.parse().ok()?;
+    Some((year, day))
+}
+
+/// Parse a header line of the form `# 2024 day 17`
+fn parse_header(line: &str) -> Option<(u32, u32)> {
+    let (year, day) = line.strip_prefix("# ")?.split_once(" day ")?;
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if year.len() != 4 || !is_number(year) || day.len() > 2 || !is_number(day) {
+        return None;
+    }
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// Remove the header from an input if it has one. Return an error if the
+/// header names another puzzle than `expected` (if it is known).
+fn strip_header<'a>(
+    path: &Path,
+    input: &'a str,
+    expected: Option<(u32, u32)>,
+) -> Result<&'a str, InputError> {
+    let (first, rest) = input.split_once('\n').unwrap_or((input, ""));
+    let Some(found) = parse_header(first.trim_end()) else {
+        return Ok(input);
+    };
+    match expected {
+        Some(expected) if expected != found => Err(InputError::WrongPuzzle {
+            path: path.to_path_buf(),
+            expected,
+            found,
+        }),
+        _ => Ok(rest),
+    }
+}
+
+/// Read a puzzle input from the given file or from stdin if `path` is `-`.
+/// Convert Windows line endings to Unix line endings. Otherwise, the input is
+/// returned unchanged (solutions rely on whether it ends with a newline).
+///
+/// Inputs collected from elsewhere (e.g. from teammates) may start with a
+/// header line such as `# 2024 day 17`. It is removed, and if the current
+/// directory is the project directory of another puzzle (e.g. `2024/day18`),
+/// an error is returned.
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref().to_path_buf();
+    let input = if path == Path::new("-") {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(&path)
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(InputError::Missing { path });
+        }
+        Err(source) => return Err(InputError::Io { path, source }),
+    };
+    let expected = env::current_dir().ok().and_then(|dir| puzzle_id(&dir));
+    let input = strip_header(&path, &input, expected)?;
+    check_input(&path, input)?;
+
+    Ok(input.replace("\r\n", "\n"))
+}
+
+fn check_input(path: &Path, input: &str) -> Result<(), InputError> {
+    if input.trim().is_empty() {
+        return Err(InputError::Empty {
+            path: path.to_path_buf(),
+        });
+    }
+    for (needle, reason) in ERROR_PAGES {
+        if input.len() < 1000 && input.contains(needle) {
+            return Err(InputError::NotAnInput {
+                path: path.to_path_buf(),
+                reason,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Make sure no line in the input ends with spaces or tabs. Useful for
+/// puzzles whose inputs are grids that must not be padded.
+pub fn check_no_trailing_whitespace(input: &str) -> Result<(), InputError> {
+    for (i, l) in input.lines().enumerate() {
+        if l.ends_with([' ', '\t']) {
+            return Err(InputError::TrailingWhitespace { line: i + 1 });
+        }
+    }
+    Ok(())
+}
+
+/// Get the path of the input file from the first command-line argument. Fall
+/// back to `default_path` if there is no argument. `-` means stdin.
+pub fn input_path(default_path: &str) -> String {
+    env::args()
+        .nth(1)
+        .unwrap_or_else(|| default_path.to_string())
+}
+
+/// Read the input (see [input_path] and [read_input]). Print the error to
+/// stderr and exit with a non-zero status if it cannot be read.
+pub fn read_input_or_exit(default_path: &str) -> String {
+    read_input(input_path(default_path)).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::scan;
+
+    fn write_temp(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc_input_test_{name}.txt"));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_input() {
+        let path = write_temp("crlf", "1 2\r\n3 4\r\n\r\n");
+        assert_eq!(read_input(&path).unwrap(), "1 2\n3 4\n\n");
+
+        let path = write_temp("no_newline", "1 2");
+        assert_eq!(read_input(&path).unwrap(), "1 2");
+    }
+
+    #[test]
+    fn test_read_input_errors() {
+        assert!(matches!(
+            read_input("does_not_exist.txt"),
+            Err(InputError::Missing { .. })
+        ));
+
+        let path = write_temp("empty", "\n");
+        assert!(matches!(read_input(&path), Err(InputError::Empty { .. })));
+
+        let path = write_temp(
+            "not_logged_in",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+        assert!(matches!(
+            read_input(&path),
+            Err(InputError::NotAnInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_header() {
+        assert_eq!(puzzle_id(Path::new("/aoc/2024/day07")), Some((2024, 7)));
+        assert_eq!(puzzle_id(Path::new("/aoc/template")), None);
+
+        let path = Path::new("input.txt");
+        let input = "# 2024 day 7\n1 2\n";
+        assert_eq!(strip_header(path, input, Some((2024, 7))).unwrap(), "1 2\n");
+        assert_eq!(strip_header(path, input, None).unwrap(), "1 2\n");
+        assert_eq!(
+            strip_header(path, input, Some((2024, 8)))
+                .unwrap_err()
+                .to_string(),
+            "\"input.txt\" is the input of 2024 day 7, not of 2024 day 8"
+        );
+
+        // grids and comments are not headers
+        for input in ["#.#\n...\n", "# 2024 day seven\n", "# year 2024 day 7\n"] {
+            assert_eq!(strip_header(path, input, Some((2024, 8))).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_check_no_trailing_whitespace() {
+        assert!(check_no_trailing_whitespace("#.#\n.#.\n").is_ok());
+        assert!(matches!(
+            check_no_trailing_whitespace("#.#\n.#. \n"),
+            Err(InputError::TrailingWhitespace { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e: InputError = scan::<[u64; 1]>("a={}", "a=x").unwrap_err().into();
+        assert_eq!(
+            e.to_string(),
+            "could not parse input: line 1, column 3: invalid value `x`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// An error that occurred while parsing the input. `line` and `column` are
+/// 1-based and point to the offending character.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A slice of the input that remembers where it starts, so errors can point
+/// to the right line and column
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Span {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Create an error pointing to the start of this span
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    /// Get the sub-span `start..end` (byte offsets into `self.text`)
+    pub fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        let before = &self.text[..start];
+        let (line, column) = match before.rfind('\n') {
+            Some(i) => (
+                self.line + before.matches('\n').count(),
+                before[i + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + before.chars().count()),
+        };
+        Span {
+            text: &self.text[start..end],
+            line,
+            column,
+        }
+    }
+
+    /// Remove leading and trailing whitespace
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+        self.slice(start, end)
+    }
+
+    /// Iterate over all lines (without line terminators)
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        let mut offset = 0;
+        self.text.split_inclusive('\n').map(move |l| {
+            let start = offset;
+            offset += l.len();
+            let l = l.strip_suffix('\n').unwrap_or(l);
+            let l = l.strip_suffix('\r').unwrap_or(l);
+            self.slice(start, start + l.len())
+        })
+    }
+
+    /// Split into blocks separated by one or more blank lines
+    pub fn blocks(&self) -> Vec<Span<'a>> {
+        let mut result = Vec::new();
+        let mut current: Option<(usize, usize)> = None;
+        let mut offset = 0;
+        for l in self.text.split_inclusive('\n') {
+            let start = offset;
+            offset += l.len();
+            if l.trim().is_empty() {
+                if let Some((s, e)) = current.take() {
+                    result.push(self.slice(s, e));
+                }
+            } else {
+                let end = start + l.trim_end_matches(['\n', '\r']).len();
+                current = Some((current.map_or(start, |c| c.0), end));
+            }
+        }
+        if let Some((s, e)) = current {
+            result.push(self.slice(s, e));
+        }
+        result
+    }
+
+    /// Split at every occurrence of `separator`
+    pub fn split(self, separator: &'a str) -> impl Iterator<Item = Span<'a>> {
+        let mut offset = 0;
+        self.text.split(separator).map(move |p| {
+            let start = offset;
+            offset += p.len() + separator.len();
+            self.slice(start, start + p.len())
+        })
+    }
+
+    /// Parse the (trimmed) span into a value of type `T`
+    pub fn parse<T>(&self) -> ParseResult<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let s = self.trim();
+        s.text
+            .parse()
+            .map_err(|e| s.error(format!("invalid value `{}`: {e}", s.text)))
+    }
+
+    /// Parse a list of values separated by `separator` (e.g. `","`). Values
+    /// are trimmed. Empty values are skipped.
+    pub fn list<T>(&self, separator: &'a str) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.split(separator)
+            .filter(|p| !p.text.trim().is_empty())
+            .map(|p| p.parse())
+            .collect()
+    }
+
+    /// Parse a list of values separated by whitespace (including newlines)
+    pub fn words<T>(&self) -> ParseResult<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut result = Vec::new();
+        let mut start = None;
+        for (i, c) in self.text.char_indices() {
+            if c.is_whitespace() {
+                if let Some(s) = start.take() {
+                    result.push(self.slice(s, i).parse()?);
+                }
+            } else if start.is_none() {
+                start = Some(i);
+            }
+        }
+        if let Some(s) = start {
+            result.push(self.slice(s, self.text.len()).parse()?);
+        }
+        Ok(result)
+    }
+
+    /// Match the span against a `pattern` and return the text captured by
+    /// each `{}` placeholder. All other characters in the pattern must match
+    /// literally. Use `{{` and `}}` to match literal braces. A placeholder
+    /// captures everything up to the next literal text (or the end of the span
+    /// if it is the last thing in the pattern), so placeholders must be
+    /// separated by literal text.
+    pub fn captures(&self, pattern: &str) -> ParseResult<Vec<Span<'a>>> {
+        let tokens = tokenize_pattern(pattern);
+        if tokens
+            .windows(2)
+            .any(|w| matches!(w, [Token::Placeholder, Token::Placeholder]))
+        {
+            return Err(self.error(format!(
+                "ambiguous pattern `{pattern}`: placeholders must be separated"
+            )));
+        }
+        let mut result = Vec::new();
+        let mut pos = 0;
+        for (i, t) in tokens.iter().enumerate() {
+            let rest = &self.text[pos..];
+            match t {
+                Token::Literal(l) => {
+                    if !rest.starts_with(l.as_str()) {
+                        let found = rest.lines().next().unwrap_or("");
+                        return Err(self
+                            .slice(pos, pos)
+                            .error(format!("expected `{l}`, found `{found}`")));
+                    }
+                    pos += l.len();
+                }
+                Token::Placeholder => {
+                    let len = match tokens.get(i + 1) {
+                        None => rest.len(),
+                        Some(Token::Literal(l)) => match rest.find(l.as_str()) {
+                            Some(len) => len,
+                            None => {
+                                return Err(self
+                                    .slice(pos, pos)
+                                    .error(format!("expected a value followed by `{l}`")));
+                            }
+                        },
+                        Some(Token::Placeholder) => unreachable!("checked above"),
+                    };
+                    result.push(self.slice(pos, pos + len));
+                    pos += len;
+                }
+            }
+        }
+        if pos < self.text.len() {
+            return Err(self
+                .slice(pos, pos)
+                .error(format!("unexpected trailing text `{}`", &self.text[pos..])));
+        }
+        Ok(result)
+    }
+
+    /// Match the span against a `pattern` (see [Span::captures]) and parse the
+    /// captured values into a tuple or array
+    pub fn scan<T: Scan>(&self, pattern: &str) -> ParseResult<T> {
+        let captures = self.captures(pattern)?;
+        if captures.len() != T::LEN {
+            return Err(self.error(format!(
+                "pattern `{pattern}` has {} placeholders but {} values were requested",
+                captures.len(),
+                T::LEN
+            )));
+        }
+        T::from_captures(&captures)
+    }
+}
+
+enum Token {
+    Literal(String),
+    Placeholder,
+}
+
+fn tokenize_pattern(pattern: &str) -> Vec<Token> {
+    let mut result = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                literal.push(c);
+                chars.next();
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                if !literal.is_empty() {
+                    result.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                result.push(Token::Placeholder);
+            }
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        result.push(Token::Literal(literal));
+    }
+    result
+}
+
+/// A tuple or array that can be created from the values captured by
+/// [Span::scan]
+pub trait Scan: Sized {
+    const LEN: usize;
+
+    fn from_captures(captures: &[Span]) -> ParseResult<Self>;
+}
+
+impl<T, const N: usize> Scan for [T; N]
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    const LEN: usize = N;
+
+    fn from_captures(captures: &[Span]) -> ParseResult<Self> {
+        let values = captures
+            .iter()
+            .map(|c| c.parse())
+            .collect::<ParseResult<Vec<T>>>()?;
+        Ok(values.try_into().ok().unwrap())
+    }
+}
+
+macro_rules! impl_scan_for_tuple {
+    ($len:expr, $($t:ident $i:tt),+) => {
+        impl<$($t),+> Scan for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Display,)+
+        {
+            const LEN: usize = $len;
+
+            fn from_captures(captures: &[Span]) -> ParseResult<Self> {
+                Ok(($(captures[$i].parse::<$t>()?,)+))
+            }
+        }
+    };
+}
+
+impl_scan_for_tuple!(1, A 0);
+impl_scan_for_tuple!(2, A 0, B 1);
+impl_scan_for_tuple!(3, A 0, B 1, C 2);
+impl_scan_for_tuple!(4, A 0, B 1, C 2, D 3);
+impl_scan_for_tuple!(5, A 0, B 1, C 2, D 3, E 4);
+impl_scan_for_tuple!(6, A 0, B 1, C 2, D 3, E 4, F 5);
+impl_scan_for_tuple!(7, A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_scan_for_tuple!(8, A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(input).lines()
+}
+
+pub fn blocks(input: &str) -> Vec<Span<'_>> {
+    Span::new(input).blocks()
+}
+
+pub fn scan<T: Scan>(pattern: &str, s: &str) -> ParseResult<T> {
+    Span::new(s).scan(pattern)
+}
+
+pub fn list<'a, T>(s: &'a str, separator: &'a str) -> ParseResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(s).list(separator)
+}
+
+pub fn words<T>(s: &str) -> ParseResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(s).words()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            scan::<[u64; 1]>("Register A: {}", "Register A: 729"),
+            Ok([729])
+        );
+        assert_eq!(
+            scan::<(String, i64, char)>("{} moves {} to {}", "robot moves -3 to x"),
+            Ok(("robot".to_string(), -3, 'x'))
+        );
+        assert_eq!(scan::<(u32, u32)>("{{{},{}}}", "{10,20}"), Ok((10, 20)));
+        assert_eq!(
+            scan::<[u64; 2]>("Button A: X+{}, Y+{}", "Button A: X+95, Y+110"),
+            Ok([95, 110])
+        );
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let err = scan::<[u64; 1]>("Register A: {}", "Register B: 729").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(
+            err.message,
+            "expected `Register A: `, found `Register B: 729`"
+        );
+
+        let err = scan::<[u64; 1]>("Register A: {}", "Register A: 7x9").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 13));
+
+        let err = scan::<[u64; 2]>("{}-{}", "10+20").unwrap_err();
+        assert_eq!(err.message, "expected a value followed by `-`");
+
+        let err = scan::<[u64; 1]>("x={},", "x=1, y=2").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+
+        let err = scan::<[u64; 2]>("x={}", "x=1").unwrap_err();
+        assert!(err.message.contains("placeholders"));
+
+        let err = scan::<[u64; 2]>("x={}{}", "x=12").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(
+            err.message,
+            "ambiguous pattern `x={}{}`: placeholders must be separated"
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let input = "Register A: 729\nRegister B: 0\n\n\nProgram: 0,1,5,4\n";
+        let blocks = blocks(input);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text, "Register A: 729\nRegister B: 0");
+        assert_eq!((blocks[1].line, blocks[1].column), (5, 1));
+
+        let registers = blocks[0]
+            .lines()
+            .map(|l| l.scan::<(char, u64)>("Register {}: {}"))
+            .collect::<ParseResult<Vec<_>>>();
+        assert_eq!(registers, Ok(vec![('A', 729), ('B', 0)]));
+
+        let program = blocks[1].captures("Program: {}").unwrap()[0];
+        assert_eq!((program.line, program.column), (5, 10));
+        assert_eq!(program.list::<u64>(","), Ok(vec![0, 1, 5, 4]));
+    }
+
+    #[test]
+    fn test_list_and_words() {
+        assert_eq!(list::<u64>("0,1, 5 ,4", ","), Ok(vec![0, 1, 5, 4]));
+        assert_eq!(list::<u64>("", ","), Ok(vec![]));
+        assert_eq!(words::<i64>("  1 -2\n3\t4 "), Ok(vec![1, -2, 3, 4]));
+
+        let err = list::<u64>("1,2,\n3,x", ",").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: invalid value `x`: invalid digit found in string"
+        );
+
+        let err = lines("a 1\nb 2\nc three")
+            .map(|l| l.scan::<(char, u32)>("{} {}"))
+            .collect::<ParseResult<Vec<_>>>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt::{Display, Formatter};
+
+mod fonts;
+
+/// An error that occurred while recognizing text
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum OcrError {
+    /// The image does not contain any lit pixels
+    Empty,
+
+    /// The height of the text (without blank rows) does not match any of the
+    /// known fonts
+    UnsupportedHeight(usize),
+
+    /// A glyph at the given column could not be recognized. `bitmap` contains
+    /// its pixels (`#` for lit pixels and `.` for unlit ones).
+    UnknownGlyph { column: usize, bitmap: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            OcrError::Empty => write!(f, "image does not contain any text"),
+            OcrError::UnsupportedHeight(h) => write!(
+                f,
+                "text is {h} pixels high but only fonts with 6 or 10 pixels are supported"
+            ),
+            OcrError::UnknownGlyph { column, bitmap } => {
+                write!(f, "unknown glyph at column {column}:\n{bitmap}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+struct Glyph {
+    c: char,
+    width: usize,
+    pixels: Vec<Vec<bool>>,
+}
+
+/// Parse a font and sort its glyphs by width (widest first), so wide glyphs
+/// are tried before narrower ones that might match a part of them
+fn parse_font(font: &[(char, &str)]) -> Vec<Glyph> {
+    let mut result = font
+        .iter()
+        .map(|(c, bitmap)| {
+            let pixels = bitmap
+                .lines()
+                .map(|l| l.chars().map(|p| p == '#').collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            Glyph {
+                c: *c,
+                width: pixels[0].len(),
+                pixels,
+            }
+        })
+        .collect::<Vec<_>>();
+    result.sort_by_key(|g| std::cmp::Reverse(g.width));
+    result
+}
+
+/// Recognize text in an image given as rows of pixels (`true` means the
+/// pixel is lit). Supports the two fonts Advent of Code uses for its
+/// block-letter answers (6 and 10 pixels high). Blank rows and columns around
+/// the text are ignored.
+pub fn recognize<R: AsRef<[bool]>>(rows: &[R]) -> Result<String, OcrError> {
+    let Some(first) = rows.iter().position(|r| r.as_ref().contains(&true)) else {
+        return Err(OcrError::Empty);
+    };
+    let last = rows
+        .iter()
+        .rposition(|r| r.as_ref().contains(&true))
+        .unwrap();
+    let rows = &rows[first..=last];
+
+    let height = rows.len();
+    let glyphs = match height {
+        6 => parse_font(fonts::SMALL),
+        10 => parse_font(fonts::LARGE),
+        _ => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let width = rows.iter().map(|r| r.as_ref().len()).max().unwrap();
+    let pixel = |x: usize, y: usize| rows[y].as_ref().get(x).copied().unwrap_or(false);
+    let is_blank_column = |x: usize| (0..height).all(|y| !pixel(x, y));
+
+    let mut result = String::new();
+    let mut x = 0;
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let glyph = glyphs.iter().find(|g| {
+            (0..height).all(|y| (0..g.width).all(|dx| g.pixels[y][dx] == pixel(x + dx, y)))
+        });
+
+        let Some(glyph) = glyph else {
+            let mut end = x;
+            while end < width && !is_blank_column(end) {
+                end += 1;
+            }
+            let bitmap = (0..height)
+                .map(|y| {
+                    (x..end)
+                        .map(|x| if pixel(x, y) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Err(OcrError::UnknownGlyph { column: x, bitmap });
+        };
+
+        result.push(glyph.c);
+        x += glyph.width;
+    }
+
+    Ok(result)
+}
+
+/// Recognize text in an image given as rows of characters. `#` and `█` are
+/// considered lit pixels. All other characters are unlit. See [recognize].
+pub fn recognize_chars<R: AsRef<[char]>>(rows: &[R]) -> Result<String, OcrError> {
+    let rows = rows
+        .iter()
+        .map(|r| {
+            r.as_ref()
+                .iter()
+                .map(|c| *c == '#' || *c == '█')
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    recognize(&rows)
+}
+
+/// Recognize text in a multi-line string. See [recognize_chars].
+pub fn recognize_str(s: &str) -> Result<String, OcrError> {
+    let rows = s
+        .lines()
+        .map(|l| l.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    recognize_chars(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Render `text` with the given font and `spacing` blank columns between
+    /// the letters
+    fn render(text: &str, font: &[(char, &str)], spacing: usize) -> String {
+        let glyphs = text
+            .chars()
+            .map(|c| font.iter().find(|g| g.0 == c).unwrap().1)
+            .map(|g| g.lines().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(spacing))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_small_font() {
+        let alphabet = fonts::SMALL.iter().map(|g| g.0).collect::<String>();
+        assert_eq!(
+            recognize_str(&render(&alphabet, fonts::SMALL, 1)),
+            Ok(alphabet)
+        );
+        assert_eq!(
+            recognize_str(&render("EHZRUGCL", fonts::SMALL, 1)),
+            Ok("EHZRUGCL".to_string())
+        );
+        assert_eq!(
+            recognize_str(&render("YAY", fonts::SMALL, 0)),
+            Ok("YAY".to_string())
+        );
+    }
+
+    #[test]
+    fn test_large_font() {
+        let alphabet = fonts::LARGE.iter().map(|g| g.0).collect::<String>();
+        assert_eq!(
+            recognize_str(&render(&alphabet, fonts::LARGE, 2)),
+            Ok(alphabet)
+        );
+    }
+
+    #[test]
+    fn test_padding_and_block_chars() {
+        let art = render("HI", fonts::SMALL, 1)
+            .lines()
+            .map(|l| format!("  {}   ", l.replace('#', "█").replace('.', " ")))
+            .collect::<Vec<_>>();
+        let mut rows = vec![vec![' '; 12]];
+        rows.extend(art.iter().map(|l| l.chars().collect::<Vec<_>>()));
+        rows.push(vec![' '; 3]);
+        assert_eq!(recognize_chars(&rows), Ok("HI".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(recognize_str("...\n..."), Err(OcrError::Empty));
+        assert_eq!(
+            recognize_str("#\n#\n#"),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+
+        let art = render("AB", fonts::SMALL, 1).replacen("###.", "#.#.", 1);
+        assert_eq!(
+            recognize_str(&art),
+            Err(OcrError::UnknownGlyph {
+                column: 5,
+                bitmap: "#.#.\n#..#\n###.\n#..#\n#..#\n###.".to_string()
+            })
+        );
+    }
+}
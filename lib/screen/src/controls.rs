@@ -0,0 +1,172 @@
+use std::{
+    io::{IsTerminal, stdin},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, unbounded};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+/// How often the input thread checks if it should stop
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A playback control triggered by a key press
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Control {
+    /// Pause or resume the visualization (space)
+    TogglePause,
+
+    /// Pause and show the previous frame from the history (left arrow)
+    StepBack,
+
+    /// Pause and show the next frame (right arrow)
+    StepForward,
+
+    /// Double the frames per second (`+`)
+    Faster,
+
+    /// Halve the frames per second (`-`)
+    Slower,
+
+    /// Restore the terminal and stop drawing (`q` or escape)
+    Quit,
+
+    /// Restore the terminal and exit like after SIGINT (Ctrl+C, which does
+    /// not send a signal in raw mode)
+    Interrupt,
+
+    /// The terminal has been resized to the given number of columns and rows
+    Resize(usize, usize),
+}
+
+/// A key press that changes which part of the grid a [crate::WindowedScreen]
+/// shows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ViewControl {
+    /// Move the window by a quarter of its size in the given direction and
+    /// stop following the camera (`w`, `a`, `s`, `d`)
+    Pan(isize, isize),
+
+    /// Show fewer grid cells per screen cell (`i`)
+    ZoomIn,
+
+    /// Show more grid cells per screen cell (`o`)
+    ZoomOut,
+
+    /// Follow the camera again (`f`)
+    Follow,
+
+    /// Show or hide the minimap (`m`)
+    ToggleMinimap,
+}
+
+/// Reads key presses in raw mode on a separate thread and converts them to
+/// playback controls and view controls. Also reports when the terminal is
+/// resized.
+pub(crate) struct Controls {
+    stop: Arc<AtomicBool>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl Controls {
+    /// Enable raw mode and start reading key presses. Return `None` if stdin
+    /// is not a terminal or raw mode is not available.
+    pub fn start() -> Option<(Self, Receiver<Control>, Receiver<ViewControl>)> {
+        if !stdin().is_terminal() || terminal::enable_raw_mode().is_err() {
+            return None;
+        }
+
+        let (sender, receiver) = unbounded();
+        let (view_sender, view_receiver) = unbounded();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let thread_handle = thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                if !event::poll(POLL_INTERVAL).unwrap_or(false) {
+                    continue;
+                }
+                let key = match event::read() {
+                    Ok(Event::Key(key)) => key,
+                    Ok(Event::Resize(cols, rows)) => {
+                        if sender
+                            .send(Control::Resize(cols as usize, rows as usize))
+                            .is_err()
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                let view_control = match key.code {
+                    KeyCode::Char('w') => Some(ViewControl::Pan(0, -1)),
+                    KeyCode::Char('a') => Some(ViewControl::Pan(-1, 0)),
+                    KeyCode::Char('s') => Some(ViewControl::Pan(0, 1)),
+                    KeyCode::Char('d') => Some(ViewControl::Pan(1, 0)),
+                    KeyCode::Char('i') => Some(ViewControl::ZoomIn),
+                    KeyCode::Char('o') => Some(ViewControl::ZoomOut),
+                    KeyCode::Char('f') => Some(ViewControl::Follow),
+                    KeyCode::Char('m') => Some(ViewControl::ToggleMinimap),
+                    _ => None,
+                };
+                if let Some(view_control) = view_control {
+                    // nobody might be listening (e.g. for a plain Screen)
+                    let _ = view_sender.send(view_control);
+                    continue;
+                }
+
+                let control = match key.code {
+                    KeyCode::Char(' ') => Control::TogglePause,
+                    KeyCode::Left => Control::StepBack,
+                    KeyCode::Right => Control::StepForward,
+                    KeyCode::Char('+') | KeyCode::Char('=') => Control::Faster,
+                    KeyCode::Char('-') => Control::Slower,
+                    KeyCode::Char('q') | KeyCode::Esc => Control::Quit,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        Control::Interrupt
+                    }
+                    _ => continue,
+                };
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Some((
+            Self {
+                stop,
+                thread_handle: Some(thread_handle),
+            },
+            receiver,
+            view_receiver,
+        ))
+    }
+
+    /// Stop reading key presses and disable raw mode
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread_handle) = self.thread_handle.take() {
+            thread_handle.join().expect("Input thread panicked");
+        }
+        terminal::disable_raw_mode().unwrap();
+    }
+}
+
+impl Drop for Controls {
+    fn drop(&mut self) {
+        if self.thread_handle.is_some() {
+            self.stop();
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crossterm::style::{Color, ContentStyle, StyledContent};
+
+/// A changed cell of a frame (index and new content)
+pub(crate) type Change = (usize, (char, ContentStyle));
+
+/// A frame buffer that can be modified in place. It keeps track of the cells
+/// that have been set since the frame was last presented, so only those have
+/// to be sent to the renderer.
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<(char, ContentStyle)>,
+
+    /// Indices of the cells that have been set since the last presentation
+    /// and a flag for each cell to avoid duplicates
+    changes: Vec<usize>,
+    changed: Vec<bool>,
+}
+
+impl Frame {
+    /// Create an empty frame with `width * height` cells
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![(' ', ContentStyle::new()); width * height],
+            changes: Vec::new(),
+            changed: vec![false; width * height],
+        }
+    }
+
+    /// Get the width of the frame
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the frame
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the character of the cell at the given position
+    pub fn get(&self, x: usize, y: usize) -> char {
+        self.cells[y * self.width + x].0
+    }
+
+    /// Set the character of the cell at the given position and reset its
+    /// style
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        self.set_cell(y * self.width + x, (c, ContentStyle::new()));
+    }
+
+    /// Set the character and the foreground color of the cell at the given
+    /// position
+    pub fn set_with_color(&mut self, x: usize, y: usize, c: char, color: (u8, u8, u8)) {
+        let mut style = ContentStyle::new();
+        style.foreground_color = Some(Color::Rgb {
+            r: color.0,
+            g: color.1,
+            b: color.2,
+        });
+        self.set_cell(y * self.width + x, (c, style));
+    }
+
+    /// Set the character and the style of the cell at the given position
+    pub fn set_styled(&mut self, x: usize, y: usize, c: StyledContent<char>) {
+        self.set_cell(y * self.width + x, (*c.content(), *c.style()));
+    }
+
+    /// Set all cells of the rectangle with the top-left corner `(x, y)` and
+    /// the size `width * height` to the given styled character
+    pub fn fill(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        c: StyledContent<char>,
+    ) {
+        for cy in y..(y + height).min(self.height) {
+            for cx in x..(x + width).min(self.width) {
+                self.set_styled(cx, cy, c);
+            }
+        }
+    }
+
+    /// Set a cell and remember that it has changed
+    fn set_cell(&mut self, i: usize, cell: (char, ContentStyle)) {
+        self.cells[i] = cell;
+        if !self.changed[i] {
+            self.changed[i] = true;
+            self.changes.push(i);
+        }
+    }
+
+    /// Append the changed cells inside the window from `(min_x, min_y)`
+    /// (inclusive) to `(max_x, max_y)` (exclusive) to `changes` and forget
+    /// about all changes. The indices are converted to a grid with rows of
+    /// `cols` cells whose top-left corner is the window's. If `all` is `true`,
+    /// append all cells of the window (e.g. because it has moved).
+    pub(crate) fn take_window_changes(
+        &mut self,
+        (min_x, min_y, max_x, max_y): (usize, usize, usize, usize),
+        cols: usize,
+        all: bool,
+        changes: &mut Vec<Change>,
+    ) {
+        if all {
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    changes.push((
+                        (y - min_y) * cols + x - min_x,
+                        self.cells[y * self.width + x],
+                    ));
+                }
+            }
+        }
+        for &i in &self.changes {
+            self.changed[i] = false;
+            let (x, y) = (i % self.width, i / self.width);
+            if !all && (min_x..max_x).contains(&x) && (min_y..max_y).contains(&y) {
+                changes.push(((y - min_y) * cols + x - min_x, self.cells[i]));
+            }
+        }
+        self.changes.clear();
+    }
+
+    /// Get all cells of the frame
+    pub(crate) fn cells(&self) -> &[(char, ContentStyle)] {
+        &self.cells
+    }
+
+    /// Forget about all changes (e.g. because the whole frame is drawn)
+    pub(crate) fn clear_changes(&mut self) {
+        for &i in &self.changes {
+            self.changed[i] = false;
+        }
+        self.changes.clear();
+    }
+
+    /// Append all changed cells to `changes` and forget about them
+    pub(crate) fn take_changes(&mut self, changes: &mut Vec<Change>) {
+        for &i in &self.changes {
+            self.changed[i] = false;
+            changes.push((i, self.cells[i]));
+        }
+        self.changes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::ContentStyle;
+
+    use super::Frame;
+
+    #[test]
+    fn test_changes() {
+        let mut frame = Frame::new(4, 3);
+        frame.set(1, 1, 'a');
+        frame.set(1, 1, 'b');
+        frame.set(3, 2, 'c');
+
+        let mut changes = Vec::new();
+        frame.take_changes(&mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                (5, ('b', ContentStyle::new())),
+                (11, ('c', ContentStyle::new()))
+            ]
+        );
+
+        changes.clear();
+        frame.take_changes(&mut changes);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_window_changes() {
+        let mut frame = Frame::new(4, 3);
+        frame.set(0, 0, 'a');
+        frame.set(2, 1, 'b');
+
+        // only the change inside the window is translated
+        let mut changes = Vec::new();
+        frame.take_window_changes((1, 1, 3, 3), 2, false, &mut changes);
+        assert_eq!(changes, vec![(1, ('b', ContentStyle::new()))]);
+
+        // all cells of the window
+        changes.clear();
+        frame.take_window_changes((0, 0, 2, 1), 2, true, &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                (0, ('a', ContentStyle::new())),
+                (1, (' ', ContentStyle::new()))
+            ]
+        );
+    }
+}
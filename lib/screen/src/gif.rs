@@ -0,0 +1,306 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Result, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::image::{Image, Rgb};
+
+/// Maximum number of codes in the LZW dictionary
+const MAX_CODES: u16 = 4096;
+
+/// Shortest frame delay (in hundredths of a second) that viewers display
+/// reliably. Frames that would be shown for a shorter time are merged.
+const MIN_DELAY: u64 = 2;
+
+/// Writes LZW codes with a variable number of bits into GIF data sub-blocks
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compress color indices with GIF's variant of LZW
+fn lzw_encode(indices: &[u8], min_code_size: u32) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bits: 0,
+    };
+    let mut dictionary = HashMap::<(u16, u8), u16>::new();
+    let mut next_code = end + 1;
+    let mut code_size = min_code_size + 1;
+    writer.write(clear, code_size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+    let mut prefix = first as u16;
+    for &k in rest {
+        if let Some(&code) = dictionary.get(&(prefix, k)) {
+            prefix = code;
+            continue;
+        }
+
+        writer.write(prefix, code_size);
+        if next_code < MAX_CODES {
+            dictionary.insert((prefix, k), next_code);
+            next_code += 1;
+            if next_code > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            // the dictionary is full - start over
+            writer.write(clear, code_size);
+            dictionary.clear();
+            next_code = end + 1;
+            code_size = min_code_size + 1;
+        }
+        prefix = k as u16;
+    }
+    writer.write(prefix, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+/// Convert an image to a palette with at most 256 colors and a color index
+/// per pixel. Images with more colors are reduced to 8 bits per pixel (3 bits
+/// red, 3 bits green, 2 bits blue).
+fn to_indexed(image: &Image) -> (Vec<Rgb>, Vec<u8>) {
+    let mut palette = Vec::new();
+    let mut lookup = HashMap::new();
+    let mut indices = Vec::with_capacity(image.pixels.len());
+    for &p in &image.pixels {
+        let i = *lookup.entry(p).or_insert_with(|| {
+            palette.push(p);
+            palette.len() - 1
+        });
+        if palette.len() > 256 {
+            let reduced = Image {
+                width: image.width,
+                height: image.height,
+                pixels: image
+                    .pixels
+                    .iter()
+                    .map(|&(r, g, b)| (r & 0xe0, g & 0xe0, b & 0xc0))
+                    .collect(),
+            };
+            return to_indexed(&reduced);
+        }
+        indices.push(i as u8);
+    }
+    (palette, indices)
+}
+
+/// Assembles frames into an animated GIF file
+pub(crate) struct GifEncoder {
+    file: BufWriter<File>,
+    previous: Option<Image>,
+
+    /// The frame that has not been written yet (because its delay is not
+    /// known yet) and the time it should be shown at
+    pending: Option<(Image, Duration)>,
+}
+
+/// Convert a time to hundredths of a second
+fn centiseconds(time: Duration) -> u64 {
+    (time.as_millis() as u64 + 5) / 10
+}
+
+impl GifEncoder {
+    /// Create a GIF file with the given size (in pixels) that loops forever
+    pub fn new(path: &Path, width: usize, height: usize) -> Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(b"GIF89a")?;
+
+        // logical screen descriptor without a global color table
+        file.write_all(&(width as u16).to_le_bytes())?;
+        file.write_all(&(height as u16).to_le_bytes())?;
+        file.write_all(&[0, 0, 0])?;
+
+        // application extension that makes the animation loop forever
+        file.write_all(&[0x21, 0xff, 0x0b])?;
+        file.write_all(b"NETSCAPE2.0")?;
+        file.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(Self {
+            file,
+            previous: None,
+            pending: None,
+        })
+    }
+
+    /// Add a frame that should be shown at the given time (relative to the
+    /// beginning of the animation)
+    pub fn add_frame(&mut self, image: Image, time: Duration) -> Result<()> {
+        match self.pending.take() {
+            Some((pending, start)) => {
+                let delay = centiseconds(time).saturating_sub(centiseconds(start));
+                if delay < MIN_DELAY {
+                    // replace the pending frame but keep its start time
+                    self.pending = Some((image, start));
+                } else {
+                    self.write_frame(pending, delay)?;
+                    self.pending = Some((image, time));
+                }
+            }
+            None => self.pending = Some((image, time)),
+        }
+        Ok(())
+    }
+
+    /// Write the last frame (which should be shown until `end`) and the
+    /// trailer of the file
+    pub fn finish(&mut self, end: Duration) -> Result<()> {
+        if let Some((pending, start)) = self.pending.take() {
+            let delay = centiseconds(end).saturating_sub(centiseconds(start));
+            self.write_frame(pending, delay.max(MIN_DELAY))?;
+        }
+        self.file.write_all(&[0x3b])?;
+        self.file.flush()
+    }
+
+    /// Write a frame. Only the region that changed since the previous frame is
+    /// encoded.
+    fn write_frame(&mut self, image: Image, delay: u64) -> Result<()> {
+        let (x, y, width, height) = match &self.previous {
+            Some(previous) => image.diff_rect(previous).unwrap_or((0, 0, 1, 1)),
+            None => (0, 0, image.width, image.height),
+        };
+        let region = image.crop(x, y, width, height);
+        let (palette, indices) = to_indexed(&region);
+
+        // the color table must have a size that is a power of two
+        let table_bits = (usize::BITS - (palette.len().max(2) - 1).leading_zeros()).max(1);
+        let min_code_size = table_bits.max(2);
+
+        // graphic control extension: keep the previous frame as background
+        // and set the delay
+        self.file.write_all(&[0x21, 0xf9, 0x04, 0x04])?;
+        self.file
+            .write_all(&(delay.min(u16::MAX as u64) as u16).to_le_bytes())?;
+        self.file.write_all(&[0x00, 0x00])?;
+
+        // image descriptor with a local color table
+        self.file.write_all(&[0x2c])?;
+        for v in [x, y, width, height] {
+            self.file.write_all(&(v as u16).to_le_bytes())?;
+        }
+        self.file.write_all(&[0x80 | (table_bits as u8 - 1)])?;
+        for i in 0..1 << table_bits {
+            let (r, g, b) = palette.get(i).copied().unwrap_or_default();
+            self.file.write_all(&[r, g, b])?;
+        }
+
+        // image data in sub-blocks of at most 255 bytes
+        self.file.write_all(&[min_code_size as u8])?;
+        for block in lzw_encode(&indices, min_code_size).chunks(255) {
+            self.file.write_all(&[block.len() as u8])?;
+            self.file.write_all(block)?;
+        }
+        self.file.write_all(&[0])?;
+
+        self.previous = Some(image);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lzw_encode;
+
+    /// Decompress the output of [lzw_encode]
+    fn lzw_decode(bytes: &[u8], min_code_size: u32) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut result = Vec::new();
+        let mut dictionary: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let (mut buffer, mut bits) = (0u32, 0u32);
+        let mut bytes = bytes.iter();
+        loop {
+            while bits < code_size {
+                buffer |= (*bytes.next().expect("missing end code") as u32) << bits;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            bits -= code_size;
+
+            if code == clear {
+                dictionary = (0..clear).map(|i| vec![i as u8]).collect();
+                dictionary.extend([Vec::new(), Vec::new()]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return result;
+            }
+
+            let entry = match (dictionary.get(code), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(p)) => {
+                    let mut entry = dictionary[p].clone();
+                    entry.push(dictionary[p][0]);
+                    entry
+                }
+                (None, None) => panic!("invalid code {code}"),
+            };
+            result.extend_from_slice(&entry);
+            if let Some(p) = previous
+                && dictionary.len() < 4096
+            {
+                let mut new = dictionary[p].clone();
+                new.push(entry[0]);
+                dictionary.push(new);
+                if dictionary.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let cases: Vec<(Vec<u8>, u32)> = vec![
+            (vec![], 2),
+            (vec![1], 2),
+            (vec![0, 1, 0, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 2, 3], 2),
+            // long enough to fill the dictionary and start over
+            ((0..20000u32).map(|i| (i * i / 7 % 251) as u8).collect(), 8),
+            (vec![7; 10000], 3),
+        ];
+        for (indices, min_code_size) in cases {
+            let encoded = lzw_encode(&indices, min_code_size);
+            assert_eq!(lzw_decode(&encoded, min_code_size), indices);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use crossterm::style::{Color, ContentStyle};
+
+use crate::font::{GLYPH_HEIGHT, GLYPH_WIDTH, glyph};
+
+/// Width of a cell in pixels (in [ImageMode::Text] and with a scale of 1)
+const CELL_WIDTH: usize = 6;
+
+/// Height of a cell in pixels (in [ImageMode::Text] and with a scale of 1)
+const CELL_HEIGHT: usize = 12;
+
+/// Color of cells without a foreground color
+const DEFAULT_FOREGROUND: Rgb = (229, 229, 229);
+
+/// Color of cells without a background color
+const DEFAULT_BACKGROUND: Rgb = (0, 0, 0);
+
+/// The 16 standard terminal colors (as defined by xterm)
+const ANSI_COLORS: [Rgb; 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+pub(crate) type Rgb = (u8, u8, u8);
+
+/// Specifies how the cells of a frame are converted to pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageMode {
+    /// Draw the character of each cell with a built-in bitmap font. Each cell
+    /// is 6x12 pixels large (multiplied by `scale`).
+    Text { scale: usize },
+
+    /// Draw each cell as one pixel (multiplied by `scale`) in its foreground
+    /// color, or in its background color if it is empty. Useful for grid
+    /// puzzles such as cellular automata.
+    Raw { scale: usize },
+}
+
+impl ImageMode {
+    /// Get the size of a cell in pixels
+    fn cell_size(&self) -> (usize, usize) {
+        match *self {
+            ImageMode::Text { scale } => (CELL_WIDTH * scale, CELL_HEIGHT * scale),
+            ImageMode::Raw { scale } => (scale, scale),
+        }
+    }
+
+    /// Get the size in pixels of an image with `width * height` cells
+    pub(crate) fn image_size(&self, width: usize, height: usize) -> (usize, usize) {
+        let (cell_width, cell_height) = self.cell_size();
+        (width * cell_width, height * cell_height)
+    }
+}
+
+/// Convert a terminal color to RGB. Return `None` for [Color::Reset].
+fn to_rgb(color: Color) -> Option<Rgb> {
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    Some(match color {
+        Color::Reset => return None,
+        Color::Black => ANSI_COLORS[0],
+        Color::DarkRed => ANSI_COLORS[1],
+        Color::DarkGreen => ANSI_COLORS[2],
+        Color::DarkYellow => ANSI_COLORS[3],
+        Color::DarkBlue => ANSI_COLORS[4],
+        Color::DarkMagenta => ANSI_COLORS[5],
+        Color::DarkCyan => ANSI_COLORS[6],
+        Color::Grey => ANSI_COLORS[7],
+        Color::DarkGrey => ANSI_COLORS[8],
+        Color::Red => ANSI_COLORS[9],
+        Color::Green => ANSI_COLORS[10],
+        Color::Yellow => ANSI_COLORS[11],
+        Color::Blue => ANSI_COLORS[12],
+        Color::Magenta => ANSI_COLORS[13],
+        Color::Cyan => ANSI_COLORS[14],
+        Color::White => ANSI_COLORS[15],
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::AnsiValue(v @ 0..16) => ANSI_COLORS[v as usize],
+        Color::AnsiValue(v @ 16..232) => {
+            let v = v as usize - 16;
+            (
+                CUBE_LEVELS[v / 36],
+                CUBE_LEVELS[v / 6 % 6],
+                CUBE_LEVELS[v % 6],
+            )
+        }
+        Color::AnsiValue(v) => {
+            let g = 8 + 10 * (v - 232);
+            (g, g, g)
+        }
+    })
+}
+
+/// Mix two colors. `t` is the share of `b` (between 0 and 1).
+fn blend(a: Rgb, b: Rgb, t: f32) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f32 * (1.0 - t) + b as f32 * t).round() as u8;
+    (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+}
+
+/// Check if the pixel at `(x, y)` of a cell (with a scale of 1) is covered by
+/// the shape of the given character. Block elements, braille patterns, and a
+/// few other shapes are drawn directly. All other characters are looked up in
+/// the bitmap font.
+fn is_set(c: char, x: usize, y: usize) -> bool {
+    let in_ellipse = |rx: f32, ry: f32| {
+        let dx = (x as f32 + 0.5 - CELL_WIDTH as f32 / 2.0) / rx;
+        let dy = (y as f32 + 0.5 - CELL_HEIGHT as f32 / 2.0) / ry;
+        dx * dx + dy * dy <= 1.0
+    };
+
+    match c {
+        '█' | '░' | '▒' | '▓' => true,
+        '▀' => y < CELL_HEIGHT / 2,
+        '▄' => y >= CELL_HEIGHT / 2,
+        '▌' => x < CELL_WIDTH / 2,
+        '▐' => x >= CELL_WIDTH / 2,
+        '▪' | '■' => (1..CELL_WIDTH - 1).contains(&x) && (4..CELL_HEIGHT - 4).contains(&y),
+        '●' | '⬤' => in_ellipse(3.0, 3.0),
+        '⬮' => in_ellipse(2.5, 4.5),
+        '○' => in_ellipse(3.0, 3.0) && !in_ellipse(2.0, 2.0),
+        '─' | '—' => y == CELL_HEIGHT / 2,
+        '│' => x == CELL_WIDTH / 2,
+        '\u{2800}'..='\u{28ff}' => {
+            // braille: dots 1-3 and 7 in the left column, dots 4-6 and 8 in
+            // the right one
+            const BITS: [[u32; 2]; 4] = [[0, 3], [1, 4], [2, 5], [6, 7]];
+            let (col, row) = (x / 3, y / 3);
+            let bit = BITS[row][col];
+            (c as u32 - 0x2800) & (1 << bit) != 0 && x % 3 < 2 && y % 3 < 2
+        }
+        _ => {
+            // center the glyph vertically
+            let top = (CELL_HEIGHT - GLYPH_HEIGHT) / 2;
+            x < GLYPH_WIDTH
+                && (top..top + GLYPH_HEIGHT).contains(&y)
+                && glyph(c)[x] & (1 << (y - top)) != 0
+        }
+    }
+}
+
+/// An RGB image
+#[derive(Clone, PartialEq)]
+pub(crate) struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Render a frame with `width * height` cells to an image
+    pub fn from_cells(
+        cells: &[(char, ContentStyle)],
+        width: usize,
+        height: usize,
+        mode: ImageMode,
+    ) -> Self {
+        let (cell_width, cell_height) = mode.cell_size();
+        let image_width = width * cell_width;
+        let mut pixels = vec![DEFAULT_BACKGROUND; image_width * height * cell_height];
+
+        for (i, (c, style)) in cells.iter().enumerate() {
+            let fg = style
+                .foreground_color
+                .and_then(to_rgb)
+                .unwrap_or(DEFAULT_FOREGROUND);
+            let bg = style
+                .background_color
+                .and_then(to_rgb)
+                .unwrap_or(DEFAULT_BACKGROUND);
+            let fg = match c {
+                '░' => blend(bg, fg, 0.25),
+                '▒' => blend(bg, fg, 0.5),
+                '▓' => blend(bg, fg, 0.75),
+                _ => fg,
+            };
+
+            let left = i % width * cell_width;
+            let top = i / width * cell_height;
+            for y in 0..cell_height {
+                for x in 0..cell_width {
+                    let set = match mode {
+                        ImageMode::Text { scale } => is_set(*c, x / scale, y / scale),
+                        ImageMode::Raw { .. } => *c != ' ',
+                    };
+                    pixels[(top + y) * image_width + left + x] = if set { fg } else { bg };
+                }
+            }
+        }
+
+        Self {
+            width: image_width,
+            height: height * cell_height,
+            pixels,
+        }
+    }
+
+    /// Get the smallest rectangle `(x, y, width, height)` that contains all
+    /// pixels that differ from `other`. Return `None` if the images are equal.
+    pub fn diff_rect(&self, other: &Image) -> Option<(usize, usize, usize, usize)> {
+        let mut min = (usize::MAX, usize::MAX);
+        let mut max = (0, 0);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = y * self.width + x;
+                if self.pixels[i] != other.pixels[i] {
+                    min = (min.0.min(x), min.1.min(y));
+                    max = (max.0.max(x), max.1.max(y));
+                }
+            }
+        }
+        (min.0 != usize::MAX).then(|| (min.0, min.1, max.0 - min.0 + 1, max.1 - min.1 + 1))
+    }
+
+    /// Copy a rectangular region of the image
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Image {
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in y..y + height {
+            pixels.extend_from_slice(&self.pixels[row * self.width + x..][..width]);
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::{Color, ContentStyle};
+
+    use super::{ANSI_COLORS, DEFAULT_BACKGROUND, DEFAULT_FOREGROUND, Image, ImageMode};
+
+    #[test]
+    fn test_from_cells() {
+        let red = ContentStyle {
+            foreground_color: Some(Color::Red),
+            background_color: Some(Color::Blue),
+            ..ContentStyle::default()
+        };
+        let cells = [('|', ContentStyle::default()), ('x', red), (' ', red)];
+
+        // the glyph of '|' is a line in its third column from row 0 to 6,
+        // which is centered vertically in the cell
+        let image = Image::from_cells(&cells[..1], 1, 1, ImageMode::Text { scale: 1 });
+        assert_eq!((image.width, image.height), (6, 12));
+        for y in 0..12 {
+            for x in 0..6 {
+                let expected = if x == 2 && (2..9).contains(&y) {
+                    DEFAULT_FOREGROUND
+                } else {
+                    DEFAULT_BACKGROUND
+                };
+                assert_eq!(image.pixels[y * 6 + x], expected, "pixel ({x}, {y})");
+            }
+        }
+
+        let image = Image::from_cells(&cells, 3, 1, ImageMode::Raw { scale: 2 });
+        assert_eq!((image.width, image.height), (6, 2));
+        let (grey, red, blue) = (DEFAULT_FOREGROUND, ANSI_COLORS[9], ANSI_COLORS[12]);
+        let row = [grey, grey, red, red, blue, blue];
+        assert_eq!(image.pixels, [row, row].concat());
+    }
+
+    #[test]
+    fn test_diff_and_crop() {
+        let a = Image {
+            width: 4,
+            height: 3,
+            pixels: (0..12).map(|i| (i, i, i)).collect(),
+        };
+        assert_eq!(a.diff_rect(&a), None);
+
+        let mut b = a.clone();
+        b.pixels[4 + 1] = (255, 0, 0);
+        b.pixels[2 * 4 + 2] = (0, 255, 0);
+        assert_eq!(a.diff_rect(&b), Some((1, 1, 2, 2)));
+
+        let cropped = b.crop(1, 1, 2, 2);
+        assert_eq!((cropped.width, cropped.height), (2, 2));
+        assert_eq!(
+            cropped.pixels,
+            vec![(255, 0, 0), (6, 6, 6), (9, 9, 9), (0, 255, 0)]
+        );
+    }
+}
@@ -1,112 +1,515 @@
 use std::{
-    io::{Stdout, Write, stdout},
+    fmt::{Display, Formatter},
+    io::{self, Write, stdout},
+    path::{Path, PathBuf},
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use crossbeam_channel::{Sender, bounded};
+use crossbeam_channel::{Receiver, Sender, bounded};
 use crossterm::{ExecutableCommand, cursor, terminal};
 
-use crate::renderer::{RenderMessage, Renderer};
-use crate::style::{StyledContent, style};
+use crate::controls::{Controls, ViewControl};
+use crate::frame::Change;
+use crate::overlay::Overlay;
+use crate::renderer::{RenderMessage, RenderTarget, Renderer};
+use crate::style::{Color, ContentStyle, StyledContent, style};
+use crate::viewport::Viewport;
 
+pub use crate::frame::Frame;
+pub use crate::image::ImageMode;
+pub use crate::options::Options;
+pub use crate::overlay::{Anchor, Text};
+pub use crate::palette::{Colormap, Palette};
+pub use crate::pixels::PixelMode;
+
+mod controls;
+mod font;
+mod frame;
+mod gif;
+mod image;
+mod options;
+mod overlay;
+mod palette;
+mod pixels;
+mod png;
 mod renderer;
+mod restore;
 pub mod style;
+mod viewport;
+mod window;
+
+/// Maximum number of buffers for changed cells that the renderer gives back
+/// to the screen for reuse
+const RECYCLED_PATCHES: usize = 16;
+
+/// Where a visualization is drawn to
+pub enum Output {
+    /// Draw to the terminal
+    Terminal,
+
+    /// Record all frames into an asciinema v2 file (`.cast`) instead of
+    /// drawing them. Does not need a terminal. `cols` and `rows` specify the
+    /// size of the recorded terminal.
+    Asciicast {
+        path: PathBuf,
+        cols: usize,
+        rows: usize,
+    },
+
+    /// Render all frames to images and assemble them into an animated GIF
+    /// with the frames per second of the visualization. Does not need a
+    /// terminal. `cols` and `rows` specify the size of the virtual terminal
+    /// (relevant for [WindowedScreen]).
+    Gif {
+        path: PathBuf,
+        cols: usize,
+        rows: usize,
+        mode: ImageMode,
+    },
+
+    /// Render every frame to a PNG file in the directory `dir` (named
+    /// `frame_000000.png`, `frame_000001.png`, etc.). Does not need a
+    /// terminal. `cols` and `rows` specify the size of the virtual terminal
+    /// (relevant for [WindowedScreen]).
+    Png {
+        dir: PathBuf,
+        cols: usize,
+        rows: usize,
+        mode: ImageMode,
+    },
+}
+
+impl Output {
+    /// Get the number of columns and rows of the output
+    fn size(&self) -> io::Result<(usize, usize)> {
+        match self {
+            Output::Terminal => {
+                let (cols, rows) = terminal::size()?;
+                Ok((cols as usize, rows as usize))
+            }
+            Output::Asciicast { cols, rows, .. }
+            | Output::Gif { cols, rows, .. }
+            | Output::Png { cols, rows, .. } => Ok((*cols, *rows)),
+        }
+    }
+}
+
+/// An error that occurred while creating a visualization
+#[derive(Debug)]
+pub enum ScreenError {
+    /// The terminal could not be accessed (e.g. because stdout is not a
+    /// terminal)
+    Terminal(io::Error),
+
+    /// The output has no space to draw anything
+    NoSpace { cols: usize, rows: usize },
+
+    /// The file or directory to record the frames into could not be created
+    Recording { path: PathBuf, source: io::Error },
+
+    /// An option in the `AOC_VISUALIZE` environment variable is invalid (see
+    /// [Options::parse])
+    InvalidOption(String),
+}
+
+impl Display for ScreenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            ScreenError::Terminal(e) => write!(f, "unable to access terminal: {e}"),
+            ScreenError::NoSpace { cols, rows } => write!(
+                f,
+                "output with {cols} columns and {rows} rows is too small for a visualization"
+            ),
+            ScreenError::Recording { path, source } => {
+                write!(f, "unable to create recording {path:?}: {source}")
+            }
+            ScreenError::InvalidOption(option) => {
+                write!(f, "invalid visualization option `{option}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScreenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScreenError::Terminal(e) => Some(e),
+            ScreenError::Recording { source, .. } => Some(source),
+            ScreenError::NoSpace { .. } | ScreenError::InvalidOption(_) => None,
+        }
+    }
+}
+
+/// Get the size of the output or an error if it has no space at all
+fn output_size(output: &Output) -> Result<(usize, usize), ScreenError> {
+    let (cols, rows) = output.size().map_err(ScreenError::Terminal)?;
+    if cols == 0 || rows == 0 {
+        return Err(ScreenError::NoSpace { cols, rows });
+    }
+    Ok((cols, rows))
+}
+
+/// Convert the result of creating the render target of a recording
+fn recording_target(
+    path: &Path,
+    target: io::Result<RenderTarget>,
+) -> Result<RenderTarget, ScreenError> {
+    target.map_err(|source| ScreenError::Recording {
+        path: path.to_path_buf(),
+        source,
+    })
+}
 
 pub struct Screen {
     width: usize,
     height: usize,
-    stdout: Stdout,
-    pos: (u16, u16),
+    terminal: bool,
     thread_handle: Option<JoinHandle<()>>,
     sender: Option<Sender<RenderMessage>>,
+    controls: Option<Controls>,
     finished: bool,
+    start: Instant,
+
+    /// Key presses that change which part of the grid is shown (only used by
+    /// [WindowedScreen])
+    view_controls: Option<Receiver<ViewControl>>,
+
+    /// Status lines and labels (sent to the renderer with the next frame if
+    /// they have changed)
+    overlay: Overlay,
+    overlay_changed: bool,
+
+    /// `true` if updates should wait until the frame has been drawn
+    synchronous: bool,
+
+    /// The frame buffer for in-place updates (created on first use) and
+    /// buffers for changed cells that the renderer has given back
+    frame: Option<Frame>,
+    recycled: Receiver<Vec<Change>>,
 }
 
 impl Screen {
     /// Create a new visualization with size `width * height` and the given
     /// frames per second
-    pub fn new(width: usize, height: usize, fps: u32) -> Self {
-        let (terminal_cols, terminal_rows) = terminal::size().unwrap();
-        if (terminal_rows as usize) < height {
-            eprintln!(
-                "Terminal window is not high enough. Resize your terminal \
-                window (or zoom out), so at least {height} rows can be displayed \
-                at once. Your terminal has a height of {terminal_rows} rows."
-            );
-            std::process::exit(1);
-        }
-        if (terminal_cols as usize) < width {
-            eprintln!(
-                "Terminal window is not wide enough. Resize your terminal \
-                window (or zoom out), so at least {width} columns can be displayed \
-                at once. Your terminal has a width of {terminal_cols} columns."
-            );
-            std::process::exit(1);
-        }
+    pub fn new(width: usize, height: usize, fps: u32) -> Result<Self, ScreenError> {
+        Self::with_output(width, height, fps, Output::Terminal)
+    }
 
-        let stdout = stdout();
-        let mut lock = stdout.lock();
+    /// Create a new visualization with size `width * height` and the given
+    /// frames per second that is drawn to the given output.
+    ///
+    /// If the output is smaller than the visualization, the frames are
+    /// downscaled to fit into it. Use [WindowedScreen] to show a section of
+    /// the frames in their original size instead.
+    ///
+    /// If the visualization is drawn to the terminal, it can be controlled
+    /// with the keyboard: space pauses and resumes it, the left and right
+    /// arrow keys step backwards and forwards through the most recent frames,
+    /// `+` and `-` double or halve the speed, `q` stops drawing (the program
+    /// keeps running without a visualization), and Ctrl+C exits the program.
+    /// While the visualization is paused, updates block. If the terminal is
+    /// resized (also if stdin is not a terminal and there are no controls),
+    /// it is cleared and the visualization is drawn again at the top. The
+    /// terminal is also restored if the program panics.
+    pub fn with_output(
+        width: usize,
+        height: usize,
+        fps: u32,
+        output: Output,
+    ) -> Result<Self, ScreenError> {
+        Self::with_status_lines(width, height, fps, output, 0, 0)
+    }
 
-        // make space on screen and reset cursor
-        for _ in 0..height {
-            lock.write_all(b"\n").unwrap();
+    /// Create a new visualization like [Screen::with_output] with the given
+    /// number of status lines above (`header_lines`) and below
+    /// (`footer_lines`) the grid. See [Screen::set_header] and
+    /// [Screen::set_footer].
+    pub fn with_status_lines(
+        width: usize,
+        height: usize,
+        fps: u32,
+        output: Output,
+        header_lines: usize,
+        footer_lines: usize,
+    ) -> Result<Self, ScreenError> {
+        let (cols, rows) = output_size(&output)?;
+        let overlay = Overlay::new(header_lines, footer_lines);
+        if rows <= overlay.lines() {
+            return Err(ScreenError::NoSpace { cols, rows });
         }
-        lock.execute(cursor::MoveTo(
-            0,
-            cursor::position().unwrap().1 - height as u16,
-        ))
-        .unwrap();
-        let pos = cursor::position().unwrap();
+        let viewport = Viewport::new(width, height, cols, rows - overlay.lines());
+
+        let (pos, target) = match output {
+            Output::Terminal => {
+                let stdout = stdout();
+                let mut lock = stdout.lock();
+                let rows = (viewport.rows + overlay.lines()) as u16;
+
+                // make space on screen and reset cursor
+                for _ in 0..rows {
+                    lock.write_all(b"\n").map_err(ScreenError::Terminal)?;
+                }
+                let (_, y) = cursor::position().map_err(ScreenError::Terminal)?;
+                lock.execute(cursor::MoveTo(0, y.saturating_sub(rows)))
+                    .map_err(ScreenError::Terminal)?;
+                let pos = cursor::position().map_err(ScreenError::Terminal)?;
+
+                // hide cursor
+                lock.execute(cursor::Hide).map_err(ScreenError::Terminal)?;
+
+                drop(lock);
+                (pos, RenderTarget::Terminal(stdout))
+            }
+
+            Output::Asciicast { path, cols, rows } => {
+                let target = RenderTarget::asciicast(&path, cols, rows);
+                ((0, 0), recording_target(&path, target)?)
+            }
+
+            Output::Gif { path, mode, .. } => {
+                let rows = viewport.rows + overlay.lines();
+                let target = RenderTarget::gif(&path, viewport.cols, rows, mode);
+                ((0, 0), recording_target(&path, target)?)
+            }
 
-        // hide cursor
-        lock.execute(cursor::Hide).unwrap();
+            Output::Png { dir, mode, .. } => {
+                let target = RenderTarget::png(&dir, mode);
+                ((0, 0), recording_target(&dir, target)?)
+            }
+        };
+
+        // playback controls and resize handling are only available in a
+        // terminal. Without controls (e.g. if stdin is piped), the renderer
+        // checks the terminal size itself.
+        let terminal = matches!(target, RenderTarget::Terminal(_));
+        let (controls, control_receiver, view_controls) = if terminal {
+            restore::register();
+            match Controls::start() {
+                Some((controls, receiver, view_receiver)) => {
+                    (Some(controls), Some(receiver), Some(view_receiver))
+                }
+                None => (None, None, None),
+            }
+        } else {
+            (None, None, None)
+        };
+        let poll_size = terminal && controls.is_none();
 
         // start render loop
         let (sender, receiver) = bounded::<RenderMessage>(10);
+        let (recycle_sender, recycled) = bounded(RECYCLED_PATCHES);
+        let renderer_overlay = overlay.clone();
         let thread_handle = thread::spawn(move || {
-            let mut thread = Renderer::new(width, height, Duration::from_secs(1) / fps, pos);
-            for msg in &receiver {
-                thread.render(msg, receiver.len());
+            let time_per_frame = Duration::from_secs(1) / fps;
+            let recycle = Some(recycle_sender);
+            let mut renderer = Renderer::new(
+                viewport,
+                renderer_overlay,
+                time_per_frame,
+                pos,
+                target,
+                recycle,
+            );
+            if poll_size {
+                renderer.poll_size(|| {
+                    let (cols, rows) = terminal::size().ok()?;
+                    Some((cols as usize, rows as usize))
+                });
             }
+            renderer.run(receiver, control_receiver);
         });
 
-        Self {
+        Ok(Self {
             width,
             height,
-            stdout,
-            pos,
+            terminal,
             thread_handle: Some(thread_handle),
             sender: Some(sender),
+            controls,
             finished: false,
+            start: Instant::now(),
+            view_controls,
+            overlay,
+            overlay_changed: false,
+            synchronous: false,
+            frame: None,
+            recycled,
+        })
+    }
+
+    /// Create a new visualization with size `width * height` that is
+    /// configured by the given options (see [Options::from_env]). `fps` is
+    /// used if the options do not override it.
+    pub fn with_options(
+        width: usize,
+        height: usize,
+        fps: u32,
+        options: &Options,
+    ) -> Result<Self, ScreenError> {
+        let mut screen = Self::with_status_lines(
+            width,
+            height,
+            options.fps(fps),
+            options.output(),
+            options.header_lines,
+            options.footer_lines,
+        )?;
+        screen.set_colors(options.colors);
+        Ok(screen)
+    }
+
+    /// Send a message to the renderer. Send the overlay first if it has
+    /// changed.
+    fn send(&mut self, msg: RenderMessage) {
+        if let Some(sender) = &mut self.sender {
+            if self.overlay_changed {
+                sender
+                    .send(RenderMessage::Overlay(self.overlay.clone()))
+                    .expect("Render channel is closed");
+                self.overlay_changed = false;
+            }
+            sender.send(msg).expect("Render channel is closed");
+        }
+        if self.synchronous {
+            self.wait();
         }
     }
 
-    /// Update the visualization with a new grid
-    pub fn update(&mut self, new_grid: Vec<char>) {
+    /// Block until all updates sent so far have been drawn
+    pub fn wait(&mut self) {
         if let Some(sender) = &mut self.sender {
+            let (done_sender, done_receiver) = bounded(1);
             sender
-                .send(RenderMessage::Render { new_grid })
+                .send(RenderMessage::Sync(done_sender))
                 .expect("Render channel is closed");
+            done_receiver.recv().expect("Render thread stopped");
         }
     }
 
+    /// If `synchronous` is `true`, every update blocks until its frame has
+    /// been drawn. This is useful if the visualization draws every step of
+    /// an algorithm that should not get ahead of the screen (e.g. because it
+    /// also prints to the terminal). Frames are never skipped in this mode.
+    pub fn set_synchronous(&mut self, synchronous: bool) {
+        self.synchronous = synchronous;
+    }
+
+    /// Draw the following frames with (`true`) or without colors (`false`)
+    pub fn set_colors(&mut self, colors: bool) {
+        self.send(RenderMessage::Colors(colors));
+    }
+
+    /// Get the time that has passed since the visualization was created (e.g.
+    /// to show it in a status line)
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Set the text of the header line with the given index. The text is cut
+    /// off at the end of the line. Like all changes to status lines and
+    /// labels, it becomes visible with the next update of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line` is not less than the number of header lines the
+    /// screen was created with
+    pub fn set_header(&mut self, line: usize, text: impl Into<Text>) {
+        self.overlay.header[line] = text.into();
+        self.overlay_changed = true;
+    }
+
+    /// Set the text of the footer line with the given index like
+    /// [Screen::set_header]
+    pub fn set_footer(&mut self, line: usize, text: impl Into<Text>) {
+        self.overlay.footer[line] = text.into();
+        self.overlay_changed = true;
+    }
+
+    /// Draw a label over the grid until [Screen::clear_labels] is called.
+    /// [Anchor::At] positions refer to cells of the grid.
+    pub fn add_label(&mut self, anchor: Anchor, text: impl Into<Text>) {
+        self.overlay.labels.push((anchor, text.into()));
+        self.overlay_changed = true;
+    }
+
+    /// Remove all labels added with [Screen::add_label]
+    pub fn clear_labels(&mut self) {
+        self.overlay.labels.clear();
+        self.overlay_changed = true;
+    }
+
+    /// Draw a legend over the grid that explains the meaning of symbols. Each
+    /// entry is drawn in its own line. Replaces the previous legend.
+    pub fn set_legend<S: Into<Text>, D: Into<Text>>(
+        &mut self,
+        anchor: Anchor,
+        entries: impl IntoIterator<Item = (S, D)>,
+    ) {
+        let mut legend = Text::default();
+        for (i, (symbol, description)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                legend = legend.append("\n");
+            }
+            legend = legend.append(symbol).append(" ").append(description);
+        }
+        self.overlay.legend = Some((anchor, legend));
+        self.overlay_changed = true;
+    }
+
+    /// Update the visualization with a new grid
+    pub fn update(&mut self, new_grid: Vec<char>) {
+        self.send(RenderMessage::Render { new_grid });
+    }
+
     /// Update the visualization with a new colored grid
     pub fn update_with_colors(&mut self, new_grid: Vec<(char, (u8, u8, u8))>) {
-        if let Some(sender) = &mut self.sender {
-            sender
-                .send(RenderMessage::RenderWithColors { new_grid })
-                .expect("Render channel is closed");
-        }
+        self.send(RenderMessage::RenderWithColors { new_grid });
     }
 
     /// Update the visualization with a new styled grid
     pub fn update_with_style(&mut self, new_grid: Vec<StyledContent<char>>) {
-        if let Some(sender) = &mut self.sender {
-            sender
-                .send(RenderMessage::RenderWithStyle { new_grid })
-                .expect("Render channel is closed");
-        }
+        self.send(RenderMessage::RenderWithStyle { new_grid });
+    }
+
+    /// Get the frame buffer to modify the visualization in place. Call
+    /// [Screen::present] to draw the changes. This is much faster than the
+    /// `update` methods if only a few cells of a large grid change per frame
+    /// because only the cells that have been set are sent to the renderer,
+    /// and only those are drawn. The changes are applied to the most recent
+    /// frame, even if it was drawn with another method.
+    pub fn frame_mut(&mut self) -> &mut Frame {
+        self.frame
+            .get_or_insert_with(|| Frame::new(self.width, self.height))
+    }
+
+    /// Draw all cells that have been set in the frame buffer (see
+    /// [Screen::frame_mut]) since the last call
+    pub fn present(&mut self) {
+        let mut changes = self.patch_buffer();
+        self.frame_mut().take_changes(&mut changes);
+        self.send(RenderMessage::Patch(changes));
+    }
+
+    /// Get an empty buffer for changed cells (reuse one that the renderer has
+    /// given back if possible)
+    fn patch_buffer(&mut self) -> Vec<Change> {
+        self.recycled.try_recv().unwrap_or_default()
+    }
+
+    /// Update the visualization with a buffer of pixels that are either set
+    /// or not. The buffer consists of rows with `width` pixels each, which are
+    /// packed into cells according to `mode`. The screen should have the size
+    /// returned by [PixelMode::screen_size].
+    pub fn update_pixels(&mut self, pixels: &[bool], width: usize, mode: PixelMode) {
+        self.update(mode.pack_bools(pixels, width));
+    }
+
+    /// Update the visualization with a buffer of colored pixels like
+    /// [Screen::update_pixels]. Pixels with the color [Color::Reset] are not
+    /// set.
+    pub fn update_pixels_with_colors(&mut self, pixels: &[Color], width: usize, mode: PixelMode) {
+        self.update_with_style(mode.pack_colors(pixels, width));
     }
 
     /// Finish visualization and reset terminal
@@ -115,17 +518,22 @@ impl Screen {
             return;
         }
 
-        // drop sender and wait for render thread to finish
+        // stop the controls (so a paused render thread does not wait for
+        // them anymore), drop sender, and wait for render thread to finish
+        // (it moves the cursor below the grid and shows it again)
+        if let Some(mut controls) = self.controls.take() {
+            controls.stop();
+        }
         self.sender.take();
-        if let Some(thread_handle) = self.thread_handle.take() {
-            thread_handle.join().expect("Render thread panicked");
+        if let Some(thread_handle) = self.thread_handle.take()
+            && thread_handle.join().is_err()
+            && !thread::panicking()
+        {
+            panic!("Render thread panicked");
+        }
+        if self.terminal {
+            restore::unregister();
         }
-
-        let mut stdout = self.stdout.lock();
-        stdout
-            .execute(cursor::MoveTo(0, self.pos.1 + self.height as u16 + 1))
-            .unwrap();
-        stdout.execute(cursor::Show).unwrap();
 
         self.finished = true;
     }
@@ -144,94 +552,327 @@ pub struct WindowedScreen {
     last_window_top_left: Option<(usize, usize)>,
     screen: Screen,
     forward: bool,
+
+    /// The frame buffer for in-place updates of the whole grid (created on
+    /// first use) and the window that was presented last
+    frame: Option<Frame>,
+    last_presented_window: Option<(usize, usize, usize, usize)>,
+
+    /// Each cell of the window shows `zoom * zoom` cells of the grid. At
+    /// `max_zoom`, the whole grid fits into the window.
+    zoom: usize,
+    max_zoom: usize,
+
+    /// The top-left corner of the window if it has been panned manually
+    /// (`None` if the window follows the center passed to the updates)
+    panned: Option<(isize, isize)>,
+
+    /// Where the minimap is drawn (if it is shown) and where it is drawn when
+    /// it is toggled with the keyboard
+    minimap: Option<Anchor>,
+    minimap_anchor: Anchor,
 }
 
 impl WindowedScreen {
     /// Create a new windowed visualization with size `width * height`, the
     /// given frames per second, and an optional `margin` used to determine the
-    /// position of the window around the center
-    pub fn new(width: usize, height: usize, fps: u32, margin: Option<(usize, usize)>) -> Self {
-        let (terminal_cols, terminal_rows) = terminal::size().unwrap();
-        if terminal_cols as usize >= width && terminal_rows as usize >= height {
+    /// position of the window around the center.
+    ///
+    /// In addition to the keys described in [Screen::with_output], the
+    /// window can be controlled with the keyboard: `i` and `o` zoom in and
+    /// out, `w`, `a`, `s`, and `d` pan the window (the window stops following
+    /// the center until `f` is pressed), and `m` shows or hides the minimap.
+    /// These keys take effect with the next update.
+    pub fn new(
+        width: usize,
+        height: usize,
+        fps: u32,
+        margin: Option<(usize, usize)>,
+    ) -> Result<Self, ScreenError> {
+        Self::with_output(width, height, fps, margin, Output::Terminal)
+    }
+
+    /// Create a new windowed visualization like [WindowedScreen::new] that is
+    /// drawn to the given output. If the output is too small for the margin,
+    /// the margin is reduced accordingly.
+    pub fn with_output(
+        width: usize,
+        height: usize,
+        fps: u32,
+        margin: Option<(usize, usize)>,
+        output: Output,
+    ) -> Result<Self, ScreenError> {
+        Self::with_status_lines(width, height, fps, margin, output, 0, 0)
+    }
+
+    /// Create a new windowed visualization like [WindowedScreen::with_output]
+    /// with status lines like [Screen::with_status_lines]. The window gets
+    /// the space that remains after the status lines.
+    pub fn with_status_lines(
+        width: usize,
+        height: usize,
+        fps: u32,
+        margin: Option<(usize, usize)>,
+        output: Output,
+        header_lines: usize,
+        footer_lines: usize,
+    ) -> Result<Self, ScreenError> {
+        let (terminal_cols, terminal_rows) = output_size(&output)?;
+        if terminal_rows <= header_lines + footer_lines {
+            return Err(ScreenError::NoSpace {
+                cols: terminal_cols,
+                rows: terminal_rows,
+            });
+        }
+        let terminal_rows = terminal_rows - header_lines - footer_lines;
+
+        if terminal_cols >= width && terminal_rows >= height {
             // the terminal is large enough - we don't need a window
-            let screen = Screen::new(terminal_cols as usize, terminal_rows as usize, fps);
-            return Self {
+            let screen =
+                Screen::with_status_lines(width, height, fps, output, header_lines, footer_lines)?;
+            return Ok(Self {
                 width,
                 height,
                 margin: None,
                 last_window_top_left: None,
                 screen,
                 forward: true,
-            };
-        }
-
-        if let Some(margin) = margin {
-            if (terminal_rows as usize) < margin.1 * 2 + 1 {
-                eprintln!(
-                    "Terminal window is not high enough. Resize your terminal \
-                    window (or zoom out), so at least {} rows can be displayed \
-                    at once. Your terminal has a height of {terminal_rows} rows.",
-                    margin.1 * 2 + 1
-                );
-                std::process::exit(1);
-            }
-            if (terminal_cols as usize) < margin.0 * 2 + 1 {
-                eprintln!(
-                    "Terminal window is not wide enough. Resize your terminal \
-                    window (or zoom out), so at least {} columns can be displayed \
-                    at once. Your terminal has a width of {terminal_cols} columns.",
-                    margin.0 * 2 + 1
-                );
-                std::process::exit(1);
-            }
+                frame: None,
+                last_presented_window: None,
+                zoom: 1,
+                max_zoom: 1,
+                panned: None,
+                minimap: None,
+                minimap_anchor: Anchor::TopRight,
+            });
         }
 
-        let screen = Screen::new(terminal_cols as usize, terminal_rows as usize, fps);
-        Self {
+        let margin = margin.map(|(mx, my)| {
+            (
+                mx.min((terminal_cols - 1) / 2),
+                my.min((terminal_rows - 1) / 2),
+            )
+        });
+        let max_zoom = width
+            .div_ceil(terminal_cols)
+            .max(height.div_ceil(terminal_rows));
+
+        let screen = Screen::with_status_lines(
+            terminal_cols,
+            terminal_rows,
+            fps,
+            output,
+            header_lines,
+            footer_lines,
+        )?;
+        Ok(Self {
             width,
             height,
             margin,
             last_window_top_left: None,
             screen,
             forward: false,
+            frame: None,
+            last_presented_window: None,
+            zoom: 1,
+            max_zoom,
+            panned: None,
+            minimap: None,
+            minimap_anchor: Anchor::TopRight,
+        })
+    }
+
+    /// Create a new windowed visualization like [WindowedScreen::new] that is
+    /// configured by the given options like [Screen::with_options]
+    pub fn with_options(
+        width: usize,
+        height: usize,
+        fps: u32,
+        margin: Option<(usize, usize)>,
+        options: &Options,
+    ) -> Result<Self, ScreenError> {
+        let mut screen = Self::with_status_lines(
+            width,
+            height,
+            options.fps(fps),
+            margin,
+            options.output(),
+            options.header_lines,
+            options.footer_lines,
+        )?;
+        screen.set_colors(options.colors);
+        Ok(screen)
+    }
+
+    /// Get the time that has passed since the visualization was created
+    pub fn elapsed(&self) -> Duration {
+        self.screen.elapsed()
+    }
+
+    /// Draw the following frames with or without colors (see
+    /// [Screen::set_colors])
+    pub fn set_colors(&mut self, colors: bool) {
+        self.screen.set_colors(colors);
+    }
+
+    /// Set the text of a header line (see [Screen::set_header])
+    pub fn set_header(&mut self, line: usize, text: impl Into<Text>) {
+        self.screen.set_header(line, text);
+    }
+
+    /// Set the text of a footer line (see [Screen::set_footer])
+    pub fn set_footer(&mut self, line: usize, text: impl Into<Text>) {
+        self.screen.set_footer(line, text);
+    }
+
+    /// Draw a label over the window (see [Screen::add_label]). [Anchor::At]
+    /// positions refer to cells of the window, not of the whole grid.
+    pub fn add_label(&mut self, anchor: Anchor, text: impl Into<Text>) {
+        self.screen.add_label(anchor, text);
+    }
+
+    /// Remove all labels (see [Screen::clear_labels])
+    pub fn clear_labels(&mut self) {
+        self.screen.clear_labels();
+    }
+
+    /// Draw a legend over the window (see [Screen::set_legend])
+    pub fn set_legend<S: Into<Text>, D: Into<Text>>(
+        &mut self,
+        anchor: Anchor,
+        entries: impl IntoIterator<Item = (S, D)>,
+    ) {
+        self.screen.set_legend(anchor, entries);
+    }
+
+    /// Get the current zoom level (the number of grid cells in each direction
+    /// that one cell of the window shows)
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    /// Show `zoom * zoom` grid cells in each cell of the window. Each cell
+    /// shows the most common non-blank cell of its block. The zoom level is
+    /// clamped between 1 and the level at which the whole grid fits into the
+    /// window. The window keeps its center.
+    pub fn set_zoom(&mut self, zoom: usize) {
+        let zoom = zoom.clamp(1, self.max_zoom);
+        if zoom == self.zoom {
+            return;
+        }
+
+        if let Some((min_x, min_y)) = self.panned {
+            let center_x = min_x + (self.screen.width * self.zoom / 2) as isize;
+            let center_y = min_y + (self.screen.height * self.zoom / 2) as isize;
+            self.panned = Some((
+                center_x - (self.screen.width * zoom / 2) as isize,
+                center_y - (self.screen.height * zoom / 2) as isize,
+            ));
+        } else {
+            // center the window around the next center
+            self.last_window_top_left = None;
+        }
+        self.zoom = zoom;
+    }
+
+    /// Zoom out until the whole grid fits into the window
+    pub fn zoom_to_fit(&mut self) {
+        self.set_zoom(self.max_zoom);
+    }
+
+    /// Move the window by the given number of grid cells. The window stops
+    /// following the center passed to the updates until
+    /// [WindowedScreen::follow] is called.
+    pub fn pan(&mut self, dx: isize, dy: isize) {
+        let (min_x, min_y) = self.panned.unwrap_or_else(|| {
+            let (x, y) = self.last_window_top_left.unwrap_or((0, 0));
+            (x as isize, y as isize)
+        });
+        self.panned = Some((min_x + dx, min_y + dy));
+    }
+
+    /// Let the window follow the center passed to the updates again after it
+    /// has been panned
+    pub fn follow(&mut self) {
+        self.panned = None;
+    }
+
+    /// Show a minimap with the position of the window in the whole grid at
+    /// the given corner or hide it (`None`). The minimap is never shown if
+    /// the whole grid fits on the screen.
+    pub fn set_minimap(&mut self, anchor: Option<Anchor>) {
+        if let Some(anchor) = anchor {
+            self.minimap_anchor = anchor;
+        }
+        self.minimap = anchor;
+    }
+
+    /// Apply the view controls that have been pressed since the last update
+    fn apply_view_controls(&mut self) {
+        let controls = match &self.screen.view_controls {
+            Some(receiver) => receiver.try_iter().collect::<Vec<_>>(),
+            None => return,
+        };
+        for control in controls {
+            match control {
+                ViewControl::Pan(dx, dy) => {
+                    let step_x = (self.screen.width * self.zoom / 4).max(1) as isize;
+                    let step_y = (self.screen.height * self.zoom / 4).max(1) as isize;
+                    self.pan(dx * step_x, dy * step_y);
+                }
+                ViewControl::ZoomIn => self.set_zoom(self.zoom / 2),
+                ViewControl::ZoomOut => self.set_zoom(self.zoom * 2),
+                ViewControl::Follow => self.follow(),
+                ViewControl::ToggleMinimap => {
+                    if self.minimap.is_some() {
+                        self.set_minimap(None);
+                    } else {
+                        self.set_minimap(Some(self.minimap_anchor));
+                    }
+                }
+            }
         }
     }
 
     // Calculate the extent of the window
     fn get_window(&mut self, center: (usize, usize)) -> (usize, usize, usize, usize) {
-        let center_min_x = center.0 as isize - self.screen.width as isize / 2;
-        let center_max_x = center_min_x + self.screen.width as isize;
-        let center_min_y = center.1 as isize - self.screen.height as isize / 2;
-        let center_max_y = center_min_y + self.screen.height as isize;
+        self.apply_view_controls();
+
+        let window_width = (self.screen.width * self.zoom) as isize;
+        let window_height = (self.screen.height * self.zoom) as isize;
+        let center_min_x = center.0 as isize - window_width / 2;
+        let center_max_x = center_min_x + window_width;
+        let center_min_y = center.1 as isize - window_height / 2;
+        let center_max_y = center_min_y + window_height;
 
-        let (mut min_x, mut min_y, mut max_x, mut max_y) = if let Some(margin) = self.margin {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = if let Some((min_x, min_y)) = self.panned
+        {
+            (min_x, min_y, min_x + window_width, min_y + window_height)
+        } else if let Some(margin) = self.margin {
+            let margin = (
+                (margin.0 * self.zoom) as isize,
+                (margin.1 * self.zoom) as isize,
+            );
             if let Some(last_window_top_left) = self.last_window_top_left {
                 let mut min_x = last_window_top_left.0 as isize;
                 let mut min_y = last_window_top_left.1 as isize;
-                if min_x + margin.0 as isize > center.0 as isize {
-                    let dx = min_x + margin.0 as isize - center.0 as isize;
+                if min_x + margin.0 > center.0 as isize {
+                    let dx = min_x + margin.0 - center.0 as isize;
                     min_x -= dx;
                 }
-                if (center.0 as isize) > min_x + self.screen.width as isize - margin.0 as isize {
-                    let dx = center.0 as isize
-                        - (min_x + self.screen.width as isize - margin.0 as isize);
+                if (center.0 as isize) > min_x + window_width - margin.0 {
+                    let dx = center.0 as isize - (min_x + window_width - margin.0);
                     min_x += dx;
                 }
-                if min_y + margin.1 as isize > center.1 as isize {
-                    let dy = min_y + margin.1 as isize - center.1 as isize;
+                if min_y + margin.1 > center.1 as isize {
+                    let dy = min_y + margin.1 - center.1 as isize;
                     min_y -= dy;
                 }
-                if (center.1 as isize) > min_y + self.screen.height as isize - margin.1 as isize {
-                    let dy = center.1 as isize
-                        - (min_y + self.screen.height as isize - margin.1 as isize);
+                if (center.1 as isize) > min_y + window_height - margin.1 {
+                    let dy = center.1 as isize - (min_y + window_height - margin.1);
                     min_y += dy;
                 }
-                (
-                    min_x,
-                    min_y,
-                    min_x + self.screen.width as isize,
-                    min_y + self.screen.height as isize,
-                )
+                (min_x, min_y, min_x + window_width, min_y + window_height)
             } else {
                 (center_min_x, center_min_y, center_max_x, center_max_y)
             }
@@ -267,12 +908,58 @@ impl WindowedScreen {
         }
 
         self.last_window_top_left = Some((min_x as usize, min_y as usize));
+        if self.panned.is_some() {
+            // don't let the window be panned beyond the edges of the grid
+            self.panned = Some((min_x, min_y));
+        }
 
-        (
+        let window = (
             min_x as usize,
             min_y as usize,
             max_x as usize,
             max_y as usize,
+        );
+        self.update_minimap(window);
+        window
+    }
+
+    /// Draw the minimap for the given window or remove it
+    fn update_minimap(&mut self, window: (usize, usize, usize, usize)) {
+        let minimap = self.minimap.map(|anchor| {
+            let size = window::minimap_size(
+                self.width,
+                self.height,
+                self.screen.width,
+                self.screen.height,
+            );
+            (
+                anchor,
+                window::minimap(self.width, self.height, window, size),
+            )
+        });
+
+        // only send the overlay to the renderer if the minimap has changed
+        if self.screen.overlay.minimap != minimap {
+            self.screen.overlay.minimap = minimap;
+            self.screen.overlay_changed = true;
+        }
+    }
+
+    /// Copy the window around `center` out of a grid
+    fn window<T: Copy + PartialEq>(
+        &mut self,
+        grid: &[T],
+        center: (usize, usize),
+        blank: T,
+    ) -> Vec<T> {
+        let window = self.get_window(center);
+        window::zoomed_window(
+            grid,
+            self.width,
+            window,
+            self.zoom,
+            (self.screen.width, self.screen.height),
+            blank,
         )
     }
 
@@ -281,14 +968,7 @@ impl WindowedScreen {
         if self.forward {
             self.screen.update(new_grid);
         } else {
-            let mut new_window = vec![' '; self.screen.width * self.screen.height];
-            let (min_x, min_y, max_x, max_y) = self.get_window(center);
-            for y in min_y..max_y {
-                for x in min_x..max_x {
-                    new_window[(y - min_y) * self.screen.width + (x - min_x)] =
-                        new_grid[y * self.width + x];
-                }
-            }
+            let new_window = self.window(&new_grid, center, ' ');
             self.screen.update(new_window);
         }
     }
@@ -302,14 +982,7 @@ impl WindowedScreen {
         if self.forward {
             self.screen.update_with_colors(new_grid);
         } else {
-            let mut new_window = vec![(' ', (0, 0, 0)); self.screen.width * self.screen.height];
-            let (min_x, min_y, max_x, max_y) = self.get_window(center);
-            for y in min_y..max_y {
-                for x in min_x..max_x {
-                    new_window[(y - min_y) * self.screen.width + (x - min_x)] =
-                        new_grid[y * self.width + x];
-                }
-            }
+            let new_window = self.window(&new_grid, center, (' ', (0, 0, 0)));
             self.screen.update_with_colors(new_window);
         }
     }
@@ -323,20 +996,163 @@ impl WindowedScreen {
         if self.forward {
             self.screen.update_with_style(new_grid);
         } else {
-            let mut new_window = vec![style(' '); self.screen.width * self.screen.height];
-            let (min_x, min_y, max_x, max_y) = self.get_window(center);
-            for y in min_y..max_y {
-                for x in min_x..max_x {
-                    new_window[(y - min_y) * self.screen.width + (x - min_x)] =
-                        new_grid[y * self.width + x];
-                }
-            }
+            let new_window = self.window(&new_grid, center, style(' '));
             self.screen.update_with_style(new_window);
         }
     }
 
+    /// Update the visualization with a buffer of pixels like
+    /// [Screen::update_pixels]. `center` is given in pixels.
+    pub fn update_pixels(
+        &mut self,
+        pixels: &[bool],
+        width: usize,
+        mode: PixelMode,
+        center: (usize, usize),
+    ) {
+        let center = pixel_to_cell(center, mode);
+        self.update(mode.pack_bools(pixels, width), center);
+    }
+
+    /// Update the visualization with a buffer of colored pixels like
+    /// [Screen::update_pixels_with_colors]. `center` is given in pixels.
+    pub fn update_pixels_with_colors(
+        &mut self,
+        pixels: &[Color],
+        width: usize,
+        mode: PixelMode,
+        center: (usize, usize),
+    ) {
+        let center = pixel_to_cell(center, mode);
+        self.update_with_style(mode.pack_colors(pixels, width), center);
+    }
+
+    /// Get the frame buffer to modify the whole grid in place like
+    /// [Screen::frame_mut]. Call [WindowedScreen::present] to draw the
+    /// changes.
+    pub fn frame_mut(&mut self) -> &mut Frame {
+        self.frame
+            .get_or_insert_with(|| Frame::new(self.width, self.height))
+    }
+
+    /// Draw the cells of the window around `center` that have been set in the
+    /// frame buffer since the last call. All cells of the window are drawn if
+    /// the window has moved or is zoomed out.
+    pub fn present(&mut self, center: (usize, usize)) {
+        let window = if self.forward {
+            (0, 0, self.width, self.height)
+        } else {
+            self.get_window(center)
+        };
+
+        if self.zoom > 1 {
+            // every cell of the window might be affected by the changes
+            let size = (self.screen.width, self.screen.height);
+            let frame = self
+                .frame
+                .get_or_insert_with(|| Frame::new(self.width, self.height));
+            let new_grid = window::zoomed_window(
+                frame.cells(),
+                self.width,
+                window,
+                self.zoom,
+                size,
+                (' ', ContentStyle::new()),
+            );
+            frame.clear_changes();
+            self.last_presented_window = None;
+            self.screen.send(RenderMessage::RenderCells { new_grid });
+            return;
+        }
+
+        let all = self.last_presented_window != Some(window);
+        self.last_presented_window = Some(window);
+
+        let cols = self.screen.width;
+        let mut changes = self.screen.patch_buffer();
+        self.frame_mut()
+            .take_window_changes(window, cols, all, &mut changes);
+        self.screen.send(RenderMessage::Patch(changes));
+    }
+
     // Finish visualization and reset terminal
     pub fn finish(&mut self) {
         self.screen.finish();
     }
 }
+
+/// Convert a position in a pixel buffer to the cell that contains it
+fn pixel_to_cell(pos: (usize, usize), mode: PixelMode) -> (usize, usize) {
+    let (cell_width, cell_height) = mode.cell_size();
+    (pos.0 / cell_width, pos.1 / cell_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::*;
+
+    /// Get a path for a recording in the temporary directory
+    fn temp_cast(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("screen_test_{name}.cast"))
+    }
+
+    /// Parse the header of a recording and the timestamps of its events
+    fn read_cast(path: &Path) -> (String, Vec<f64>) {
+        let recording = fs::read_to_string(path).unwrap();
+        let mut lines = recording.lines();
+        let header = lines.next().unwrap().to_string();
+        let timestamps = lines
+            .map(|l| {
+                let (t, _) = l.strip_prefix('[').unwrap().split_once(',').unwrap();
+                t.parse().unwrap()
+            })
+            .collect();
+        (header, timestamps)
+    }
+
+    #[test]
+    fn test_record_asciicast() {
+        let path = temp_cast("screen");
+        let output = Output::Asciicast {
+            path: path.clone(),
+            cols: 12,
+            rows: 6,
+        };
+        let mut screen = Screen::with_output(4, 2, 4, output).unwrap();
+        for c in ['a', 'b', 'c'] {
+            screen.update(vec![c; 8]);
+        }
+        screen.finish();
+
+        let (header, timestamps) = read_cast(&path);
+        assert!(header.starts_with(r#"{"version": 2, "width": 12, "height": 6,"#));
+
+        // hidden cursor, three frames at 4 fps, and the restored cursor after
+        // the last frame
+        assert_eq!(timestamps, vec![0.0, 0.0, 0.25, 0.5, 0.75]);
+    }
+
+    #[test]
+    fn test_record_windowed_asciicast() {
+        let path = temp_cast("windowed_screen");
+        let output = Output::Asciicast {
+            path: path.clone(),
+            cols: 4,
+            rows: 2,
+        };
+        let mut screen = WindowedScreen::with_output(10, 10, 10, None, output).unwrap();
+        for i in 0..4 {
+            let mut grid = vec![' '; 100];
+            grid[i] = '#';
+            screen.update(grid, (0, 0));
+        }
+        screen.finish();
+
+        let (header, timestamps) = read_cast(&path);
+        assert!(header.starts_with(r#"{"version": 2, "width": 4, "height": 2,"#));
+        // frames with the window's size at 10 fps
+        assert_eq!(timestamps, vec![0.0, 0.0, 0.1, 0.2, 0.3, 0.4]);
+    }
+}
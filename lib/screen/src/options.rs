@@ -0,0 +1,180 @@
+use std::{env, path::PathBuf};
+
+use crate::{ImageMode, Output, ScreenError};
+
+/// The environment variable that activates and configures visualizations
+const ENV_VAR: &str = "AOC_VISUALIZE";
+
+/// The size of the virtual terminal of recordings if no size is given
+const DEFAULT_RECORDING_SIZE: (usize, usize) = (80, 24);
+
+/// Runtime options of a visualization. Solutions compile their visualization
+/// in behind a `visualize` feature and only run it if the `AOC_VISUALIZE`
+/// environment variable is set (see [Options::from_env]):
+///
+/// ```text
+/// AOC_VISUALIZE=true cargo run --release --features visualize
+/// AOC_VISUALIZE=fps=100,output=day18.gif,size=120x40 cargo run --release --features visualize
+/// ```
+///
+/// The fields for status lines can be set by the solution itself (e.g.
+/// `Options { footer_lines: 1, ..options }`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    /// Frames per second that override the visualization's default (`fps`)
+    pub fps: Option<u32>,
+
+    /// A file to record the visualization into instead of drawing it to the
+    /// terminal (`output`). Files ending in `.cast` become asciinema
+    /// recordings and files ending in `.gif` animated GIFs. Any other path is
+    /// a directory for PNG files.
+    pub output: Option<PathBuf>,
+
+    /// Number of columns and rows of the virtual terminal of a recording
+    /// (`size`, e.g. `120x40`)
+    pub size: Option<(usize, usize)>,
+
+    /// `false` if the visualization should be drawn without colors (`colors`,
+    /// disabled by default if the `NO_COLOR` environment variable is set)
+    pub colors: bool,
+
+    /// Number of status lines above and below the grid (see
+    /// [crate::Screen::with_status_lines])
+    pub header_lines: usize,
+    pub footer_lines: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            fps: None,
+            output: None,
+            size: None,
+            colors: true,
+            header_lines: 0,
+            footer_lines: 0,
+        }
+    }
+}
+
+impl Options {
+    /// Parse the options from the `AOC_VISUALIZE` environment variable.
+    /// Return `None` if the variable is not set or if it is `false` or `0`,
+    /// in which case nothing should be visualized.
+    pub fn from_env() -> Result<Option<Self>, ScreenError> {
+        let Ok(value) = env::var(ENV_VAR) else {
+            return Ok(None);
+        };
+        let options = Self::parse(&value)?;
+        Ok(options.map(|options| Self {
+            colors: options.colors && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+            ..options
+        }))
+    }
+
+    /// Parse options from a string. `true`, `1`, and an empty string select
+    /// the default options. `false` and `0` disable the visualization.
+    /// Otherwise, the string is a comma-separated list of `key=value` pairs
+    /// with the keys `fps`, `output`, `size`, and `colors` (`true` or
+    /// `false`).
+    pub fn parse(value: &str) -> Result<Option<Self>, ScreenError> {
+        let value = value.trim();
+        match value {
+            "" | "true" | "1" => return Ok(Some(Self::default())),
+            "false" | "0" => return Ok(None),
+            _ => {}
+        }
+
+        let invalid = |option: &str| ScreenError::InvalidOption(option.to_string());
+        let mut result = Self::default();
+        for option in value.split(',') {
+            let (key, v) = option.split_once('=').ok_or_else(|| invalid(option))?;
+            let v = v.trim();
+            match key.trim() {
+                "fps" => {
+                    let fps = v.parse().map_err(|_| invalid(option))?;
+                    if fps == 0 {
+                        return Err(invalid(option));
+                    }
+                    result.fps = Some(fps);
+                }
+                "output" => result.output = Some(PathBuf::from(v)),
+                "size" => {
+                    let size = v
+                        .split_once('x')
+                        .and_then(|(c, r)| Some((c.parse().ok()?, r.parse().ok()?)))
+                        .ok_or_else(|| invalid(option))?;
+                    result.size = Some(size);
+                }
+                "colors" => {
+                    result.colors = match v {
+                        "true" | "1" | "on" => true,
+                        "false" | "0" | "off" => false,
+                        _ => return Err(invalid(option)),
+                    }
+                }
+                _ => return Err(invalid(option)),
+            }
+        }
+        Ok(Some(result))
+    }
+
+    /// Get the frames per second given by the options or `default`
+    pub fn fps(&self, default: u32) -> u32 {
+        self.fps.unwrap_or(default)
+    }
+
+    /// Get the output that the visualization should be drawn to
+    pub fn output(&self) -> Output {
+        let Some(path) = &self.output else {
+            return Output::Terminal;
+        };
+        let (cols, rows) = self.size.unwrap_or(DEFAULT_RECORDING_SIZE);
+        let mode = ImageMode::Text { scale: 1 };
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("cast") => Output::Asciicast {
+                path: path.clone(),
+                cols,
+                rows,
+            },
+            Some("gif") => Output::Gif {
+                path: path.clone(),
+                cols,
+                rows,
+                mode,
+            },
+            _ => Output::Png {
+                dir: path.clone(),
+                cols,
+                rows,
+                mode,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Options;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Options::parse("true").unwrap(), Some(Options::default()));
+        assert_eq!(Options::parse("0").unwrap(), None);
+        assert_eq!(
+            Options::parse("fps=100, output=out.gif,size=120x40,colors=off").unwrap(),
+            Some(Options {
+                fps: Some(100),
+                output: Some(PathBuf::from("out.gif")),
+                size: Some((120, 40)),
+                colors: false,
+                ..Options::default()
+            })
+        );
+        assert!(Options::parse("fps=0").is_err());
+        assert!(Options::parse("size=120").is_err());
+        assert!(Options::parse("speed=2").is_err());
+    }
+}
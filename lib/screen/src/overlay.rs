@@ -0,0 +1,220 @@
+use crossterm::style::{ContentStyle, StyledContent};
+
+use crate::viewport::Viewport;
+
+/// A text that consists of one or more spans with their own style. Line
+/// breaks (`\n`) start a new line in labels and legends.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Text {
+    spans: Vec<(String, ContentStyle)>,
+}
+
+impl Text {
+    /// Append another text to this one
+    pub fn append(mut self, other: impl Into<Text>) -> Self {
+        self.spans.extend(other.into().spans);
+        self
+    }
+
+    /// Split the text into lines of styled characters
+    pub(crate) fn lines(&self) -> Vec<Vec<(char, ContentStyle)>> {
+        let mut result = vec![Vec::new()];
+        for (s, style) in &self.spans {
+            for c in s.chars() {
+                if c == '\n' {
+                    result.push(Vec::new());
+                } else {
+                    result.last_mut().unwrap().push((c, *style));
+                }
+            }
+        }
+        result
+    }
+}
+
+impl From<&str> for Text {
+    fn from(s: &str) -> Self {
+        Self {
+            spans: vec![(s.to_string(), ContentStyle::new())],
+        }
+    }
+}
+
+impl From<String> for Text {
+    fn from(s: String) -> Self {
+        Self {
+            spans: vec![(s, ContentStyle::new())],
+        }
+    }
+}
+
+impl From<StyledContent<&str>> for Text {
+    fn from(s: StyledContent<&str>) -> Self {
+        Self {
+            spans: vec![(s.content().to_string(), *s.style())],
+        }
+    }
+}
+
+impl From<StyledContent<String>> for Text {
+    fn from(s: StyledContent<String>) -> Self {
+        Self {
+            spans: vec![(s.content().clone(), *s.style())],
+        }
+    }
+}
+
+impl From<StyledContent<char>> for Text {
+    fn from(s: StyledContent<char>) -> Self {
+        Self {
+            spans: vec![(s.content().to_string(), *s.style())],
+        }
+    }
+}
+
+/// Specifies where a label is drawn over the grid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Anchor {
+    /// The top-left corner of the label is at the given cell of the grid
+    At(usize, usize),
+
+    /// The label is drawn in one of the corners of the grid
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Status lines above and below the grid as well as labels over it
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Overlay {
+    pub header: Vec<Text>,
+    pub footer: Vec<Text>,
+    pub labels: Vec<(Anchor, Text)>,
+    pub legend: Option<(Anchor, Text)>,
+    pub minimap: Option<(Anchor, Text)>,
+}
+
+impl Overlay {
+    /// Create an overlay with the given number of header and footer lines
+    pub fn new(header_lines: usize, footer_lines: usize) -> Self {
+        Self {
+            header: vec![Text::default(); header_lines],
+            footer: vec![Text::default(); footer_lines],
+            labels: Vec::new(),
+            legend: None,
+            minimap: None,
+        }
+    }
+
+    /// Get the number of lines of the header and the footer
+    pub fn lines(&self) -> usize {
+        self.header.len() + self.footer.len()
+    }
+
+    /// Render the overlay into a buffer of cells covering the header, the
+    /// grid drawn into `viewport`, and the footer. Cells not covered by the
+    /// overlay are `None`. Header and footer lines are padded with spaces.
+    pub fn cells(&self, viewport: Viewport) -> Vec<Option<(char, ContentStyle)>> {
+        let cols = viewport.cols;
+        let rows = self.header.len() + viewport.rows + self.footer.len();
+        let mut result = vec![None; cols * rows];
+
+        let draw_line = |result: &mut Vec<Option<_>>, line: &[(char, ContentStyle)], x, y| {
+            if y >= rows {
+                return;
+            }
+            for (i, c) in line.iter().enumerate() {
+                if x + i >= cols {
+                    break;
+                }
+                result[y * cols + x + i] = Some(*c);
+            }
+        };
+
+        // status lines
+        let footer_start = self.header.len() + viewport.rows;
+        for (y, text) in self.header.iter().enumerate().chain(
+            self.footer
+                .iter()
+                .enumerate()
+                .map(|(y, t)| (footer_start + y, t)),
+        ) {
+            let blank = vec![(' ', ContentStyle::new()); cols];
+            draw_line(&mut result, &blank, 0, y);
+            draw_line(&mut result, &text.lines()[0], 0, y);
+        }
+
+        // labels, legend, and minimap
+        for (anchor, text) in self
+            .labels
+            .iter()
+            .chain(self.legend.iter())
+            .chain(self.minimap.iter())
+        {
+            let lines = text.lines();
+            let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+            let height = lines.len();
+            let (x, y) = match *anchor {
+                Anchor::At(x, y) => (
+                    x * viewport.cols / viewport.width.max(1),
+                    y * viewport.rows / viewport.height.max(1),
+                ),
+                Anchor::TopLeft => (0, 0),
+                Anchor::TopRight => (cols.saturating_sub(width), 0),
+                Anchor::BottomLeft => (0, viewport.rows.saturating_sub(height)),
+                Anchor::BottomRight => (
+                    cols.saturating_sub(width),
+                    viewport.rows.saturating_sub(height),
+                ),
+            };
+            for (i, line) in lines.iter().enumerate() {
+                if y + i < viewport.rows {
+                    draw_line(&mut result, line, x, self.header.len() + y + i);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Stylize;
+
+    use super::{Anchor, Overlay, Text};
+    use crate::viewport::Viewport;
+
+    /// Convert overlay cells to lines of characters (`.` for uncovered cells)
+    fn to_lines(
+        cells: &[Option<(char, crossterm::style::ContentStyle)>],
+        cols: usize,
+    ) -> Vec<String> {
+        cells
+            .chunks(cols)
+            .map(|row| row.iter().map(|c| c.map_or('.', |c| c.0)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_status_lines_and_labels() {
+        let mut overlay = Overlay::new(1, 1);
+        overlay.header[0] = "Score: 5".into();
+        overlay.footer[0] = Text::from("a").append("b".red());
+        overlay.labels.push((Anchor::At(1, 1), "x\ny".into()));
+        overlay.legend = Some((Anchor::BottomRight, "#=wall".into()));
+
+        let cells = overlay.cells(Viewport::new(10, 3, 10, 3));
+        assert_eq!(
+            to_lines(&cells, 10),
+            vec![
+                "Score: 5  ",
+                "..........",
+                ".x........",
+                ".y..#=wall",
+                "ab        "
+            ]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use std::ops::RangeInclusive;
+
+use crossterm::style::Color;
+
+/// Coefficients of polynomials (lowest degree first) that approximate the
+/// red, green, and blue channels of the Turbo colormap
+const TURBO: [[f64; 6]; 3] = [
+    [
+        0.13572138,
+        4.61539260,
+        -42.66032258,
+        132.13108234,
+        -152.94239396,
+        59.28637943,
+    ],
+    [
+        0.09140261,
+        2.19418839,
+        4.84296658,
+        -14.18503333,
+        4.27729857,
+        2.82956604,
+    ],
+    [
+        0.10667330,
+        12.64194608,
+        -60.58204836,
+        110.36276771,
+        -89.90310912,
+        27.34824973,
+    ],
+];
+
+/// Coefficients of polynomials (lowest degree first) that approximate the
+/// red, green, and blue channels of the Viridis colormap
+const VIRIDIS: [[f64; 7]; 3] = [
+    [
+        0.2777273272234177,
+        0.1050930431085774,
+        -0.3308618287255563,
+        -4.634230498983486,
+        6.228269936347081,
+        4.776384997670288,
+        -5.435455855934631,
+    ],
+    [
+        0.005407344544966578,
+        1.404613529898575,
+        0.214847559468213,
+        -5.799100973351585,
+        14.17993336680509,
+        -13.74514537774601,
+        4.645852612178535,
+    ],
+    [
+        0.3340998053353061,
+        1.384590162594685,
+        0.09509516302823659,
+        -19.33244095627987,
+        56.69055260068105,
+        -65.35303263337234,
+        26.3124352495832,
+    ],
+];
+
+/// Evaluate a polynomial at `t` and convert the result to a color channel
+fn channel(coefficients: &[f64], t: f64) -> u8 {
+    let v = coefficients.iter().rev().fold(0.0, |acc, c| acc * t + c);
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// A continuous color scale that maps numbers between 0 and 1 to colors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Colormap {
+    /// Google's rainbow colormap from dark blue over green and yellow to dark
+    /// red
+    Turbo,
+
+    /// Perceptually uniform colormap from dark purple over teal to yellow
+    Viridis,
+
+    /// From black to white
+    Grayscale,
+}
+
+impl Colormap {
+    /// Get the color for `t` (clamped to the range 0 to 1)
+    pub fn color(&self, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let (r, g, b) = match self {
+            Colormap::Turbo => (
+                channel(&TURBO[0], t),
+                channel(&TURBO[1], t),
+                channel(&TURBO[2], t),
+            ),
+            Colormap::Viridis => (
+                channel(&VIRIDIS[0], t),
+                channel(&VIRIDIS[1], t),
+                channel(&VIRIDIS[2], t),
+            ),
+            Colormap::Grayscale => {
+                let v = (t * 255.0).round() as u8;
+                (v, v, v)
+            }
+        };
+        Color::Rgb { r, g, b }
+    }
+
+    /// Get the color for a `value` between `min` and `max`
+    pub fn scale(&self, value: f64, min: f64, max: f64) -> Color {
+        if max > min {
+            self.color((value - min) / (max - min))
+        } else {
+            self.color(0.0)
+        }
+    }
+}
+
+/// A list of colors for categorical data (e.g. one color per object)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    /// Create a palette from a list of colors
+    pub fn new(colors: Vec<Color>) -> Self {
+        assert!(!colors.is_empty(), "A palette needs at least one color");
+        Self { colors }
+    }
+
+    /// Create a palette with `n` colors evenly sampled from the given range of
+    /// a colormap
+    pub fn from_colormap(colormap: Colormap, range: RangeInclusive<f64>, n: usize) -> Self {
+        let (start, end) = range.into_inner();
+        let colors = (0..n)
+            .map(|i| {
+                let t = if n > 1 {
+                    i as f64 / (n - 1) as f64
+                } else {
+                    0.0
+                };
+                colormap.color(start + (end - start) * t)
+            })
+            .collect();
+        Self::new(colors)
+    }
+
+    /// Shuffle the colors, so neighboring categories get colors that are
+    /// easier to tell apart. The same `seed` always leads to the same order.
+    pub fn shuffled(mut self, seed: u64) -> Self {
+        // xorshift64 (the state must not be 0)
+        let mut state = seed.max(1);
+        for i in (1..self.colors.len()).rev() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let j = (state % (i as u64 + 1)) as usize;
+            self.colors.swap(i, j);
+        }
+        self
+    }
+
+    /// Get the number of colors in the palette
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Check if the palette has no colors (never true, since [Palette::new]
+    /// requires at least one color)
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Get the color for category `i`. Colors repeat if there are more
+    /// categories than colors.
+    pub fn get(&self, i: usize) -> Color {
+        self.colors[i % self.colors.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use super::{Colormap, Palette};
+
+    #[test]
+    fn test_colormap_ends() {
+        assert_eq!(
+            Colormap::Grayscale.color(-1.0),
+            Color::Rgb { r: 0, g: 0, b: 0 }
+        );
+        assert_eq!(
+            Colormap::Grayscale.scale(10.0, 0.0, 10.0),
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(
+            Colormap::Turbo.color(0.0),
+            Color::Rgb {
+                r: 35,
+                g: 23,
+                b: 27
+            }
+        );
+        assert_eq!(
+            Colormap::Viridis.color(1.0),
+            Color::Rgb {
+                r: 252,
+                g: 231,
+                b: 33
+            }
+        );
+    }
+
+    #[test]
+    fn test_shuffled_palette() {
+        let palette = Palette::from_colormap(Colormap::Grayscale, 0.0..=1.0, 6);
+        let shuffled = palette.clone().shuffled(42);
+        assert_eq!(shuffled, palette.clone().shuffled(42));
+        assert_ne!(shuffled, palette);
+
+        let mut colors = (0..6).map(|i| shuffled.get(i)).collect::<Vec<_>>();
+        colors.sort_by_key(|c| match c {
+            Color::Rgb { r, .. } => *r,
+            _ => unreachable!(),
+        });
+        assert_eq!(colors, (0..6).map(|i| palette.get(i)).collect::<Vec<_>>());
+        assert_eq!(shuffled.get(6), shuffled.get(0));
+    }
+}
@@ -0,0 +1,258 @@
+use crossterm::style::{Color, ContentStyle, StyledContent};
+
+/// Bit of a braille character (relative to U+2800) for each dot. Dots 1-3 and
+/// 7 are in the left column, dots 4-6 and 8 in the right one.
+const BRAILLE_BITS: [[u32; 2]; 4] = [[0, 3], [1, 4], [2, 5], [6, 7]];
+
+/// Specifies how a pixel buffer is packed into the cells of a screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PixelMode {
+    /// Two pixels per cell (one above the other) drawn with the upper and
+    /// lower half block characters. Colored pixels get independent colors
+    /// because the upper pixel is drawn in the foreground color and the lower
+    /// one in the background color.
+    HalfBlock,
+
+    /// Eight pixels per cell (2 columns and 4 rows) drawn with braille
+    /// patterns. All pixels of a cell share one foreground color.
+    Braille,
+}
+
+impl PixelMode {
+    /// Get the number of pixels per cell horizontally and vertically
+    pub fn cell_size(&self) -> (usize, usize) {
+        match self {
+            PixelMode::HalfBlock => (1, 2),
+            PixelMode::Braille => (2, 4),
+        }
+    }
+
+    /// Get the number of columns and rows of a screen that can display
+    /// `width * height` pixels
+    pub fn screen_size(&self, width: usize, height: usize) -> (usize, usize) {
+        let (cell_width, cell_height) = self.cell_size();
+        (width.div_ceil(cell_width), height.div_ceil(cell_height))
+    }
+
+    /// Convert a pixel buffer with rows of `width` pixels into cells. `f` is
+    /// called for each cell with a function that returns the pixel at a given
+    /// offset in the cell (or `None` if it is outside the buffer).
+    fn pack<P: Copy, T>(
+        &self,
+        pixels: &[P],
+        width: usize,
+        mut f: impl FnMut(&dyn Fn(usize, usize) -> Option<P>) -> T,
+    ) -> Vec<T> {
+        let height = pixels.len().div_ceil(width);
+        let (cell_width, cell_height) = self.cell_size();
+        let (cols, rows) = self.screen_size(width, height);
+
+        let mut cells = Vec::with_capacity(cols * rows);
+        for row in 0..rows {
+            for col in 0..cols {
+                let get = |dx: usize, dy: usize| {
+                    let x = col * cell_width + dx;
+                    let y = row * cell_height + dy;
+                    if x < width {
+                        pixels.get(y * width + x).copied()
+                    } else {
+                        None
+                    }
+                };
+                cells.push(f(&get));
+            }
+        }
+        cells
+    }
+
+    /// Pack a buffer of pixels that are either set or not into characters.
+    /// The buffer consists of rows with `width` pixels each.
+    pub(crate) fn pack_bools(&self, pixels: &[bool], width: usize) -> Vec<char> {
+        self.pack(pixels, width, |get| {
+            let set = |dx, dy| get(dx, dy).unwrap_or(false);
+            match self {
+                PixelMode::HalfBlock => match (set(0, 0), set(0, 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                },
+                PixelMode::Braille => braille(set),
+            }
+        })
+    }
+
+    /// Pack a buffer of colored pixels into styled characters. Pixels with the
+    /// color [Color::Reset] are not set. The buffer consists of rows with
+    /// `width` pixels each.
+    pub(crate) fn pack_colors(&self, pixels: &[Color], width: usize) -> Vec<StyledContent<char>> {
+        self.pack(pixels, width, |get| {
+            let color = |dx, dy| get(dx, dy).unwrap_or(Color::Reset);
+            match self {
+                PixelMode::HalfBlock => {
+                    // the default foreground color is visible, so an unset
+                    // pixel must always be drawn in the background color
+                    let (c, fg, bg) = match (color(0, 0), color(0, 1)) {
+                        (Color::Reset, Color::Reset) => (' ', Color::Reset, Color::Reset),
+                        (Color::Reset, bottom) => ('▄', bottom, Color::Reset),
+                        (top, bottom) => ('▀', top, bottom),
+                    };
+                    let mut style = ContentStyle::new();
+                    style.foreground_color = Some(fg);
+                    style.background_color = Some(bg);
+                    StyledContent::new(style, c)
+                }
+                PixelMode::Braille => {
+                    let c = braille(|dx, dy| color(dx, dy) != Color::Reset);
+                    let colors = (0..4)
+                        .flat_map(|dy| [color(0, dy), color(1, dy)])
+                        .filter(|&c| c != Color::Reset)
+                        .collect::<Vec<_>>();
+                    let mut style = ContentStyle::new();
+                    style.foreground_color = Some(average(&colors));
+                    StyledContent::new(style, c)
+                }
+            }
+        })
+    }
+}
+
+/// Get the braille pattern with a dot at each offset in the cell for which
+/// `set` returns true
+fn braille(set: impl Fn(usize, usize) -> bool) -> char {
+    let mut bits = 0;
+    for (dy, row) in BRAILLE_BITS.iter().enumerate() {
+        for (dx, bit) in row.iter().enumerate() {
+            if set(dx, dy) {
+                bits |= 1 << bit;
+            }
+        }
+    }
+    char::from_u32(0x2800 + bits).unwrap()
+}
+
+/// Calculate the average of the given colors. Colors that are not RGB are only
+/// kept if all colors are the same.
+fn average(colors: &[Color]) -> Color {
+    let Some(&first) = colors.first() else {
+        return Color::Reset;
+    };
+    if colors.iter().all(|&c| c == first) {
+        return first;
+    }
+
+    let mut sum = (0, 0, 0);
+    let mut n = 0;
+    for &c in colors {
+        if let Color::Rgb { r, g, b } = c {
+            sum = (sum.0 + r as u32, sum.1 + g as u32, sum.2 + b as u32);
+            n += 1;
+        }
+    }
+    if n == 0 {
+        return first;
+    }
+    Color::Rgb {
+        r: (sum.0 / n) as u8,
+        g: (sum.1 / n) as u8,
+        b: (sum.2 / n) as u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use super::PixelMode;
+
+    #[test]
+    fn test_screen_size() {
+        let cases = [
+            (PixelMode::HalfBlock, (4, 4), (4, 2)),
+            (PixelMode::HalfBlock, (3, 5), (3, 3)),
+            (PixelMode::Braille, (4, 8), (2, 2)),
+            (PixelMode::Braille, (5, 9), (3, 3)),
+        ];
+        for (mode, (width, height), expected) in cases {
+            assert_eq!(mode.screen_size(width, height), expected, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn test_half_blocks() {
+        #[rustfmt::skip]
+        let pixels = [
+            true, true,  false, false,
+            true, false, true,  false,
+            true, false, false, true,
+        ];
+        let cells = PixelMode::HalfBlock.pack_bools(&pixels, 4);
+        // the missing row below the last one counts as unset
+        assert_eq!(cells, vec!['█', '▀', '▄', ' ', '▀', ' ', ' ', '▀']);
+
+        let (red, blue) = (Color::Red, Color::Rgb { r: 0, g: 0, b: 255 });
+        #[rustfmt::skip]
+        let pixels = [
+            red,  Color::Reset, red,          Color::Reset,
+            blue, blue,         Color::Reset, Color::Reset,
+        ];
+        let cells = PixelMode::HalfBlock.pack_colors(&pixels, 4);
+        let expected = [
+            ('▀', red, blue),
+            ('▄', blue, Color::Reset),
+            ('▀', red, Color::Reset),
+            (' ', Color::Reset, Color::Reset),
+        ];
+        assert_eq!(cells.len(), expected.len());
+        for (cell, (c, fg, bg)) in cells.iter().zip(expected) {
+            assert_eq!(*cell.content(), c);
+            assert_eq!(cell.style().foreground_color, Some(fg), "{c}");
+            assert_eq!(cell.style().background_color, Some(bg), "{c}");
+        }
+    }
+
+    #[test]
+    fn test_braille() {
+        // the dot of each pixel of a 2x4 block
+        let dots = [
+            ((0, 0), '⠁'),
+            ((0, 1), '⠂'),
+            ((0, 2), '⠄'),
+            ((1, 0), '⠈'),
+            ((1, 1), '⠐'),
+            ((1, 2), '⠠'),
+            ((0, 3), '⡀'),
+            ((1, 3), '⢀'),
+        ];
+        for ((x, y), expected) in dots {
+            let mut pixels = [false; 8];
+            pixels[y * 2 + x] = true;
+            assert_eq!(
+                PixelMode::Braille.pack_bools(&pixels, 2),
+                vec![expected],
+                "({x}, {y})"
+            );
+        }
+
+        // a 3x5 buffer covers the right column and the bottom rows of its
+        // last cells only partially
+        let cells = PixelMode::Braille.pack_bools(&[true; 15], 3);
+        assert_eq!(cells, vec!['⣿', '⡇', '⠉', '⠁']);
+
+        // a cell's color is the average of the colors of its dots
+        let (red, blue) = (
+            Color::Rgb { r: 255, g: 0, b: 0 },
+            Color::Rgb { r: 0, g: 0, b: 255 },
+        );
+        let cells = PixelMode::Braille.pack_colors(&[red, Color::Reset, Color::Reset, blue], 2);
+        assert_eq!(*cells[0].content(), '⠑');
+        assert_eq!(
+            cells[0].style().foreground_color,
+            Some(Color::Rgb {
+                r: 127,
+                g: 0,
+                b: 127
+            })
+        );
+    }
+}
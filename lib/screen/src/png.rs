@@ -0,0 +1,130 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Result, Write},
+    path::Path,
+};
+
+use crate::image::Image;
+
+/// Maximum length of an uncompressed deflate block
+const MAX_STORED_BLOCK: usize = 65535;
+
+/// Calculate the CRC-32 checksum used by PNG chunks
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xedb88320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Calculate the Adler-32 checksum used by zlib streams
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &d in data {
+        a = (a + d as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Wrap data into a zlib stream without compressing it
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        result.extend_from_slice(&[0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        result.push(last as u8);
+        result.extend_from_slice(&len.to_le_bytes());
+        result.extend_from_slice(&(!len).to_le_bytes());
+        result.extend_from_slice(block);
+    }
+    result.extend_from_slice(&adler32(data).to_be_bytes());
+    result
+}
+
+/// Write a PNG chunk
+fn write_chunk(file: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> Result<()> {
+    file.write_all(&(data.len() as u32).to_be_bytes())?;
+    file.write_all(kind)?;
+    file.write_all(data)?;
+    let mut checked = kind.to_vec();
+    checked.extend_from_slice(data);
+    file.write_all(&crc32(&checked).to_be_bytes())
+}
+
+/// Save an image as an (uncompressed) RGB PNG file
+pub(crate) fn write_png(path: &Path, image: &Image) -> Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&(image.width as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filter, and interlacing
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(&mut file, b"IHDR", &header)?;
+
+    // every row starts with its filter type (0 = none)
+    let mut raw = Vec::with_capacity((image.width * 3 + 1) * image.height);
+    for row in image.pixels.chunks(image.width) {
+        raw.push(0);
+        for &(r, g, b) in row {
+            raw.extend_from_slice(&[r, g, b]);
+        }
+    }
+    write_chunk(&mut file, b"IDAT", &zlib_stored(&raw))?;
+    write_chunk(&mut file, b"IEND", &[])?;
+
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_STORED_BLOCK, adler32, crc32, zlib_stored};
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn test_zlib_stored() {
+        assert_eq!(
+            zlib_stored(&[]),
+            vec![
+                0x78, 0x01, 0x01, 0x00, 0x00, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01
+            ]
+        );
+
+        // data that does not fit into one block is split at the maximum
+        // block length
+        let data = (0..MAX_STORED_BLOCK + 10)
+            .map(|i| i as u8)
+            .collect::<Vec<_>>();
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 5 + MAX_STORED_BLOCK + 5 + 10 + 4);
+        assert_eq!(stream[..2], [0x78, 0x01]);
+        assert_eq!(stream[2..7], [0x00, 0xff, 0xff, 0x00, 0x00]);
+        assert_eq!(stream[7..7 + MAX_STORED_BLOCK], data[..MAX_STORED_BLOCK]);
+        let second = 7 + MAX_STORED_BLOCK;
+        assert_eq!(stream[second..second + 5], [0x01, 0x0a, 0x00, 0xf5, 0xff]);
+        assert_eq!(stream[second + 5..second + 15], data[MAX_STORED_BLOCK..]);
+        assert_eq!(stream[second + 15..], adler32(&data).to_be_bytes());
+    }
+}
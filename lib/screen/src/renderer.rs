@@ -1,55 +1,603 @@
 use std::{
-    io::{Stdout, Write, stdout},
+    collections::VecDeque,
+    fs::File,
+    io::{BufWriter, Stdout, Write},
+    path::{Path, PathBuf},
     thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
+use crossbeam_channel::{Receiver, Sender, never, select, tick};
 use crossterm::{
     QueueableCommand, cursor,
     style::{
-        self, Attributes, Color, ContentStyle, SetAttributes, SetBackgroundColor,
-        SetForegroundColor, SetUnderlineColor, StyledContent,
+        self, Attribute, Attributes, Color, ContentStyle, SetAttribute, SetAttributes,
+        SetBackgroundColor, SetForegroundColor, SetUnderlineColor, StyledContent,
     },
+    terminal,
 };
 
-/// A message that can be sent to the render thread
+use crate::{
+    controls::Control,
+    frame::Change,
+    gif::GifEncoder,
+    image::{Image, ImageMode},
+    overlay::Overlay,
+    png::write_png,
+    viewport::Viewport,
+};
+
+/// Maximum size of the changes of all frames in the history together in
+/// bytes (the history contains at least one frame)
+const MAX_HISTORY_BYTES: usize = 16 << 20;
+
+/// How often the size of the terminal is checked if there are no controls
+/// that report resizes
+const RESIZE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Range of the time per frame that can be set with the playback controls
+const TIME_PER_FRAME_RANGE: (Duration, Duration) =
+    (Duration::from_micros(100), Duration::from_secs(5));
+
+/// A message that can be sent to the render thread. The grids have the size
+/// of the frames of the renderer's [Viewport].
 pub(crate) enum RenderMessage {
-    Render { new_grid: Vec<char> },
-    RenderWithColors { new_grid: Vec<(char, (u8, u8, u8))> },
-    RenderWithStyle { new_grid: Vec<StyledContent<char>> },
+    Render {
+        new_grid: Vec<char>,
+    },
+    RenderWithColors {
+        new_grid: Vec<(char, (u8, u8, u8))>,
+    },
+    RenderWithStyle {
+        new_grid: Vec<StyledContent<char>>,
+    },
+    RenderCells {
+        new_grid: Vec<(char, ContentStyle)>,
+    },
+
+    /// Change some cells of the most recent frame
+    Patch(Vec<Change>),
+
+    /// Replace the status lines and labels. The change becomes visible with
+    /// the next frame.
+    Overlay(Overlay),
+
+    /// Draw cells with (`true`) or without colors (`false`). The change
+    /// becomes visible with the next frame.
+    Colors(bool),
+
+    /// Notify the sender when all previous frames have been drawn
+    Sync(Sender<()>),
+}
+
+impl RenderMessage {
+    /// Get the character and style of the grid cell with the given index
+    fn cell(&self, i: usize) -> (char, ContentStyle) {
+        match self {
+            RenderMessage::Render { new_grid } => (new_grid[i], ContentStyle::new()),
+            RenderMessage::RenderWithColors { new_grid } => {
+                let (c, (r, g, b)) = new_grid[i];
+                let mut cs = ContentStyle::new();
+                cs.foreground_color = Some(Color::Rgb { r, g, b });
+                (c, cs)
+            }
+            RenderMessage::RenderWithStyle { new_grid } => {
+                (*new_grid[i].content(), *new_grid[i].style())
+            }
+            RenderMessage::RenderCells { new_grid } => new_grid[i],
+            RenderMessage::Patch(_)
+            | RenderMessage::Overlay(_)
+            | RenderMessage::Colors(_)
+            | RenderMessage::Sync(_) => (' ', ContentStyle::new()),
+        }
+    }
+}
+
+/// Where the renderer draws its frames to
+pub(crate) enum RenderTarget {
+    /// A live terminal
+    Terminal(Stdout),
+
+    /// An asciinema v2 recording (`.cast` file)
+    Asciicast(BufWriter<File>),
+
+    /// An animated GIF
+    Gif {
+        encoder: GifEncoder,
+        mode: ImageMode,
+    },
+
+    /// A directory with one PNG file per frame
+    Png {
+        dir: PathBuf,
+        mode: ImageMode,
+        frames_written: usize,
+    },
+
+    /// Nothing (after the user has quit the visualization, which keeps
+    /// running without being drawn)
+    Discard,
+}
+
+impl RenderTarget {
+    /// Create an asciinema v2 recording at `path` for a terminal with the
+    /// given number of columns and rows and write its header
+    pub fn asciicast(path: &Path, cols: usize, rows: usize) -> std::io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        writeln!(
+            file,
+            r#"{{"version": 2, "width": {cols}, "height": {rows}, "timestamp": {timestamp}}}"#
+        )?;
+        Ok(Self::Asciicast(file))
+    }
+
+    /// Create an animated GIF at `path` for frames with `width * height`
+    /// cells
+    pub fn gif(path: &Path, width: usize, height: usize, mode: ImageMode) -> std::io::Result<Self> {
+        let (image_width, image_height) = mode.image_size(width, height);
+        let encoder = GifEncoder::new(path, image_width, image_height)?;
+        Ok(Self::Gif { encoder, mode })
+    }
+
+    /// Create a directory at `dir` to save one PNG file per frame in
+    pub fn png(dir: &Path, mode: ImageMode) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self::Png {
+            dir: dir.to_path_buf(),
+            mode,
+            frames_written: 0,
+        })
+    }
+}
+
+/// A cell that changed between two frames of the history: its index in the
+/// drawn grid, the cell before, and the cell after the change
+type HistoryChange = (usize, (char, ContentStyle), (char, ContentStyle));
+
+/// Returns the number of columns and rows of the terminal (if it is known)
+type TerminalSize = Box<dyn FnMut() -> Option<(usize, usize)> + Send>;
+
+/// Escape a string so it can be used as a JSON string literal
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            c if (c as u32) < 0x20 || c == '\u{7f}' => {
+                result.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
 }
 
 /// Renders grid to the screen
 pub(crate) struct Renderer {
-    width: usize,
-    height: usize,
+    viewport: Viewport,
     time_per_frame: Duration,
-    stdout: Stdout,
+    target: RenderTarget,
+    buffer: Vec<u8>,
     pos: (u16, u16),
+
+    /// The status lines and labels drawn around and over the grid
+    overlay: Overlay,
+
+    /// The rendered overlay (see [Overlay::cells])
+    overlay_cells: Vec<Option<(char, ContentStyle)>>,
+
+    /// `false` if cells should be drawn without colors
+    colors: bool,
+
+    /// `true` if the overlay or the colors have changed since the grid was
+    /// last drawn completely
+    redraw: bool,
+
+    /// The cells currently drawn (with the size of the viewport's area plus
+    /// the status lines)
     last_grid: Vec<(char, ContentStyle)>,
+
+    /// The most recent frame (kept to redraw it if the terminal is resized
+    /// and to apply patches to it)
+    last_frame: Option<RenderMessage>,
+
+    /// Returns the buffers of patches to the screen
+    recycle: Option<Sender<Vec<Change>>>,
     first_render: Option<Instant>,
     frames_rendered: u32,
+
+    /// The most recently rendered frames (only kept if there are playback
+    /// controls). Each frame is stored as the drawn cells that changed since
+    /// the frame before, so a frame costs as much as its changes.
+    history: VecDeque<Vec<HistoryChange>>,
+
+    /// The size of the history in bytes and its maximum (0 if no history is
+    /// kept)
+    history_bytes: usize,
+    max_history_bytes: usize,
+
+    /// The changes of the frame that is being drawn (only recorded if a
+    /// history is kept)
+    changes: Vec<HistoryChange>,
+
+    /// The index of the history frame currently shown (if the user is
+    /// stepping through the history)
+    history_pos: Option<usize>,
+    paused: bool,
+
+    /// Returns the current size of the terminal if the renderer has to check
+    /// for resizes itself (see [Renderer::poll_size])
+    terminal_size: Option<TerminalSize>,
+
+    /// The size of the terminal when it was last checked
+    last_size: Option<(usize, usize)>,
 }
 
 impl Renderer {
-    /// Create a new renderer
-    pub fn new(width: usize, height: usize, time_per_frame: Duration, pos: (u16, u16)) -> Self {
-        let last_grid = vec![(' ', ContentStyle::new()); width * height];
-        Self {
-            width,
-            height,
+    /// Create a new renderer that draws frames into the area of the given
+    /// viewport with the overlay's status lines above and below it
+    pub fn new(
+        viewport: Viewport,
+        overlay: Overlay,
+        time_per_frame: Duration,
+        pos: (u16, u16),
+        target: RenderTarget,
+        recycle: Option<Sender<Vec<Change>>>,
+    ) -> Self {
+        let rows = viewport.rows + overlay.lines();
+        let last_grid = vec![(' ', ContentStyle::new()); viewport.cols * rows];
+        let overlay_cells = overlay.cells(viewport);
+        let mut result = Self {
+            viewport,
             time_per_frame,
-            stdout: stdout(),
+            target,
+            buffer: Vec::new(),
             pos,
+            overlay,
+            overlay_cells,
+            colors: true,
+            redraw: false,
             last_grid,
+            last_frame: None,
+            recycle,
             first_render: None,
             frames_rendered: 0,
+            history: VecDeque::new(),
+            history_bytes: 0,
+            max_history_bytes: 0,
+            changes: Vec::new(),
+            history_pos: None,
+            paused: false,
+            terminal_size: None,
+            last_size: None,
+        };
+
+        // a recording starts with an empty screen without a cursor
+        if let RenderTarget::Asciicast(_) = result.target {
+            result.buffer.queue(cursor::Hide).unwrap();
+            result.write_frame(Duration::ZERO);
+        }
+
+        result
+    }
+
+    /// Check the size of the terminal regularly with `terminal_size` and
+    /// redraw if it has changed. Needed if there are no controls, which
+    /// otherwise report resizes (e.g. if stdin is piped).
+    pub fn poll_size(
+        &mut self,
+        mut terminal_size: impl FnMut() -> Option<(usize, usize)> + Send + 'static,
+    ) {
+        self.last_size = terminal_size();
+        self.terminal_size = Some(Box::new(terminal_size));
+    }
+
+    /// Resize if the terminal size has changed since it was last checked
+    fn check_size(&mut self) {
+        if let RenderTarget::Discard = self.target {
+            return;
+        }
+        let Some(terminal_size) = &mut self.terminal_size else {
+            return;
+        };
+        let size = terminal_size();
+        if size != self.last_size {
+            self.last_size = size;
+            if let Some((cols, rows)) = size {
+                self.resize(cols, rows);
+            }
+        }
+    }
+
+    /// Write the commands in the buffer to the render target or (if the
+    /// target is an image format) render the current grid to an image. `time`
+    /// is the time since the first frame (only used for recordings).
+    fn write_frame(&mut self, time: Duration) {
+        let rows = self.rows();
+        match &mut self.target {
+            RenderTarget::Terminal(stdout) => {
+                let mut stdout = stdout.lock();
+                stdout.write_all(&self.buffer).unwrap();
+                stdout.flush().unwrap();
+            }
+            RenderTarget::Asciicast(file) => {
+                if !self.buffer.is_empty() {
+                    writeln!(
+                        file,
+                        "[{:.6}, \"o\", {}]",
+                        time.as_secs_f64(),
+                        json_string(&String::from_utf8_lossy(&self.buffer))
+                    )
+                    .unwrap();
+                }
+            }
+            RenderTarget::Gif { encoder, mode } => {
+                // unchanged frames just extend the duration of the previous one
+                if !self.buffer.is_empty() || self.frames_rendered == 1 {
+                    let image = Image::from_cells(&self.last_grid, self.viewport.cols, rows, *mode);
+                    encoder.add_frame(image, time).unwrap();
+                }
+            }
+            RenderTarget::Png {
+                dir,
+                mode,
+                frames_written,
+            } => {
+                let image = Image::from_cells(&self.last_grid, self.viewport.cols, rows, *mode);
+                write_png(&dir.join(format!("frame_{frames_written:06}.png")), &image).unwrap();
+                *frames_written += 1;
+            }
+            RenderTarget::Discard => {}
+        }
+        self.buffer.clear();
+    }
+
+    /// Get the number of rows drawn including the status lines
+    fn rows(&self) -> usize {
+        self.viewport.rows + self.overlay.lines()
+    }
+
+    /// Queue commands that move the cursor below the grid and show it again
+    fn restore_cursor(&mut self) {
+        let below = match self.target {
+            RenderTarget::Terminal(_) => self.rows() + 1,
+            _ => self.rows(),
+        };
+        self.buffer
+            .queue(cursor::MoveTo(0, self.pos.1 + below as u16))
+            .unwrap();
+        self.buffer.queue(cursor::Show).unwrap();
+    }
+
+    /// Finish rendering. Move the cursor below the grid and show it again if
+    /// the frames are drawn to a terminal or recorded, and finish an animated
+    /// GIF.
+    fn finish(&mut self) {
+        let end = self.time_per_frame * self.frames_rendered;
+        match &mut self.target {
+            RenderTarget::Png { .. } | RenderTarget::Discard => {}
+            RenderTarget::Terminal(_) => {
+                self.restore_cursor();
+                self.write_frame(Duration::ZERO);
+            }
+            RenderTarget::Asciicast(_) => {
+                self.restore_cursor();
+                self.write_frame(end);
+                if let RenderTarget::Asciicast(file) = &mut self.target {
+                    file.flush().unwrap();
+                }
+            }
+            RenderTarget::Gif { encoder, .. } => encoder.finish(end).unwrap(),
+        }
+    }
+
+    /// Render all frames received from `receiver` until it is closed. If
+    /// `controls` is given, handle playback controls and keep a history of
+    /// frames.
+    pub fn run(mut self, receiver: Receiver<RenderMessage>, controls: Option<Receiver<Control>>) {
+        if controls.is_some() {
+            self.max_history_bytes = MAX_HISTORY_BYTES;
+        }
+        let mut controls = controls.unwrap_or_else(never);
+        let size_ticks = match self.terminal_size {
+            Some(_) => tick(RESIZE_POLL_INTERVAL),
+            None => never(),
+        };
+
+        loop {
+            if self.paused {
+                // don't take new frames, so the visualization waits for us.
+                // The screen closes the controls when it finishes (also if
+                // the program panics), so finish now.
+                match controls.recv() {
+                    Ok(control) => self.handle_control(control, &receiver),
+                    Err(_) => break,
+                }
+            } else {
+                select! {
+                    recv(receiver) -> msg => match msg {
+                        Ok(msg) => self.render(msg, receiver.len()),
+                        Err(_) => break,
+                    },
+                    recv(controls) -> control => match control {
+                        Ok(control) => self.handle_control(control, &receiver),
+                        Err(_) => controls = never(),
+                    },
+                    recv(size_ticks) -> _ => self.check_size(),
+                }
+            }
+
+            if let RenderTarget::Discard = self.target {
+                // the user has quit, so ignore all further key presses
+                controls = never();
+            }
+        }
+
+        self.finish();
+    }
+
+    /// React to a playback control
+    fn handle_control(&mut self, control: Control, receiver: &Receiver<RenderMessage>) {
+        match control {
+            Control::TogglePause => {
+                self.paused = !self.paused;
+                if !self.paused {
+                    // continue with the latest frame
+                    if let Some(pos) = self.history_pos.take() {
+                        self.show_history_frame(pos, self.history.len() - 1);
+                    }
+                    self.reset_timing();
+                }
+            }
+
+            Control::StepBack => {
+                self.paused = true;
+                let pos = self
+                    .history_pos
+                    .unwrap_or(self.history.len().saturating_sub(1));
+                if pos > 0 {
+                    self.history_pos = Some(pos - 1);
+                    self.show_history_frame(pos, pos - 1);
+                }
+            }
+
+            Control::StepForward => {
+                self.paused = true;
+                match self.history_pos {
+                    Some(pos) if pos + 1 < self.history.len() => {
+                        self.history_pos = Some(pos + 1);
+                        self.show_history_frame(pos, pos + 1);
+                        if pos + 2 == self.history.len() {
+                            self.history_pos = None;
+                        }
+                    }
+                    _ => {
+                        // render the next frame (if there is one)
+                        while let Ok(msg) = receiver.recv() {
+                            let is_frame = !matches!(
+                                msg,
+                                RenderMessage::Overlay(_)
+                                    | RenderMessage::Colors(_)
+                                    | RenderMessage::Sync(_)
+                            );
+                            self.reset_timing();
+                            self.render(msg, 0);
+                            if is_frame {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            Control::Faster => {
+                self.time_per_frame = (self.time_per_frame / 2).max(TIME_PER_FRAME_RANGE.0);
+                self.reset_timing();
+            }
+
+            Control::Slower => {
+                self.time_per_frame = (self.time_per_frame * 2).min(TIME_PER_FRAME_RANGE.1);
+                self.reset_timing();
+            }
+
+            Control::Resize(cols, rows) => self.resize(cols, rows),
+
+            Control::Quit => {
+                // restore the terminal here and let the visualization run to
+                // completion without drawing anything
+                self.restore_cursor();
+                self.write_frame(Duration::ZERO);
+                terminal::disable_raw_mode().unwrap();
+                self.target = RenderTarget::Discard;
+                self.paused = false;
+                self.clear_history();
+                self.max_history_bytes = 0;
+            }
+
+            Control::Interrupt => {
+                // the solution might never finish, so exit with the status
+                // of a process terminated by SIGINT
+                if !matches!(self.target, RenderTarget::Discard) {
+                    self.restore_cursor();
+                    self.write_frame(Duration::ZERO);
+                }
+                terminal::disable_raw_mode().unwrap();
+                std::process::exit(130);
+            }
+        }
+    }
+
+    /// Draw the frame at index `to` of the history by undoing or redoing the
+    /// changes between it and the frame at index `from`, which is drawn now
+    fn show_history_frame(&mut self, from: usize, to: usize) {
+        let cols = self.viewport.cols;
+        let mut writer = CellWriter::new(self.pos);
+        let mut set = |i: usize, c| {
+            self.last_grid[i] = c;
+            writer.write(&mut self.buffer, i % cols, i / cols, c);
+        };
+        if to < from {
+            // a cell might have changed several times, so undo the changes
+            // in reverse order
+            for changes in self.history.range(to + 1..=from).rev() {
+                for &(i, old, _) in changes.iter().rev() {
+                    set(i, old);
+                }
+            }
+        } else {
+            for changes in self.history.range(from + 1..=to) {
+                for &(i, _, new) in changes {
+                    set(i, new);
+                }
+            }
         }
+        writer.finish(&mut self.buffer);
+        self.write_frame(Duration::ZERO);
+    }
+
+    /// Add the changes of the frame that has just been drawn to the history
+    /// and drop the oldest frames if the history gets too large
+    fn push_history(&mut self) {
+        let changes = std::mem::take(&mut self.changes);
+        self.history_bytes += size_of_val(changes.as_slice());
+        self.history.push_back(changes);
+        while self.history_bytes > self.max_history_bytes && self.history.len() > 1 {
+            let oldest = self.history.pop_front().unwrap();
+            self.history_bytes -= size_of_val(oldest.as_slice());
+        }
+    }
+
+    /// Discard all frames of the history
+    fn clear_history(&mut self) {
+        self.history.clear();
+        self.history_bytes = 0;
+        self.history_pos = None;
+        self.changes.clear();
+    }
+
+    /// Start measuring the time between frames anew (e.g. after a pause)
+    fn reset_timing(&mut self) {
+        self.first_render = None;
+        self.frames_rendered = 0;
     }
 
     /// Try to sleep between frames to maintain fps. Return `false` if rendering
     /// should be skipped.
     fn try_sleep(&mut self, render_queue_len: usize) -> bool {
+        // recordings have their own timeline, so never sleep or skip frames
+        if !matches!(self.target, RenderTarget::Terminal(_)) {
+            self.frames_rendered += 1;
+            return true;
+        }
+
         let mut result = true;
 
         if let Some(first_render) = self.first_render {
@@ -74,128 +622,479 @@ impl Renderer {
 
     /// Render a grid to the screen
     pub fn render(&mut self, msg: RenderMessage, render_queue_len: usize) {
+        match msg {
+            RenderMessage::Overlay(overlay) => {
+                self.overlay_cells = overlay.cells(self.viewport);
+                self.overlay = overlay;
+                self.redraw = true;
+                return;
+            }
+            RenderMessage::Colors(colors) => {
+                self.colors = colors;
+                self.redraw = true;
+                return;
+            }
+            RenderMessage::Sync(done) => {
+                // the screen might not wait anymore
+                let _ = done.send(());
+                return;
+            }
+            _ => {}
+        }
+
+        if let RenderTarget::Discard = self.target {
+            return;
+        }
         if !self.try_sleep(render_queue_len) {
             return;
         }
 
-        let mut last_cursor_x = usize::MAX;
-        let mut last_cursor_y = usize::MAX;
-        let mut stdout = self.stdout.lock();
+        if let RenderMessage::Patch(changes) = msg {
+            self.patch(changes);
+        } else {
+            self.draw(&msg);
+            self.last_frame = Some(msg);
+        }
 
-        match msg {
-            RenderMessage::Render { new_grid } => {
-                for y in 0..self.height {
-                    for x in 0..self.width {
-                        let c = new_grid[y * self.width + x];
-                        if c != self.last_grid[y * self.width + x].0 {
-                            self.last_grid[y * self.width + x].0 = c;
-                            if last_cursor_x != x || last_cursor_y != y {
-                                stdout
-                                    .queue(cursor::MoveTo(
-                                        self.pos.0 + x as u16,
-                                        self.pos.1 + y as u16,
-                                    ))
-                                    .unwrap();
-                            }
-                            stdout.queue(style::Print(c)).unwrap();
-                            last_cursor_y = y;
-                            last_cursor_x = x + 1;
-                        }
-                    }
-                }
-                stdout.flush().unwrap();
+        if self.max_history_bytes > 0 {
+            self.push_history();
+        }
+
+        let time = self.time_per_frame * (self.frames_rendered - 1);
+        self.write_frame(time);
+    }
+
+    /// Draw the cells of a frame and the overlay that have changed into the
+    /// buffer
+    fn draw(&mut self, msg: &RenderMessage) {
+        let viewport = self.viewport;
+        let header = self.overlay.header.len();
+        let overlay_cells = std::mem::take(&mut self.overlay_cells);
+        self.draw_styled(|x, y| {
+            if let Some(c) = overlay_cells[y * viewport.cols + x] {
+                c
+            } else if y < header || y >= header + viewport.rows {
+                (' ', ContentStyle::new())
+            } else {
+                msg.cell(viewport.source(x, y - header))
             }
+        });
+        self.overlay_cells = overlay_cells;
+        self.redraw = false;
+    }
 
-            RenderMessage::RenderWithColors { new_grid } => {
-                let mut last_color = Color::Grey;
-
-                for y in 0..self.height {
-                    for x in 0..self.width {
-                        let (r, g, b) = new_grid[y * self.width + x].1;
-                        let fg = Color::Rgb { r, g, b };
-                        let mut cs = ContentStyle::new();
-                        cs.foreground_color = Some(fg);
-                        let c = (new_grid[y * self.width + x].0, cs);
-                        if c != self.last_grid[y * self.width + x] {
-                            self.last_grid[y * self.width + x] = c;
-                            if fg != last_color {
-                                stdout.queue(SetForegroundColor(fg)).unwrap();
-                                last_color = fg;
-                            }
-                            if last_cursor_x != x || last_cursor_y != y {
-                                stdout
-                                    .queue(cursor::MoveTo(
-                                        self.pos.0 + x as u16,
-                                        self.pos.1 + y as u16,
-                                    ))
-                                    .unwrap();
-                            }
-                            stdout.queue(style::Print(c.0)).unwrap();
-                            last_cursor_y = y;
-                            last_cursor_x = x + 1;
-                        }
-                    }
-                }
+    /// React to a resized terminal. Clear the terminal, fit the viewport into
+    /// the new size, and redraw the most recent frame at the top. The history
+    /// is discarded because its frames have the old size.
+    fn resize(&mut self, cols: usize, rows: usize) {
+        let lines = self.overlay.lines();
+        if cols == 0 || rows <= lines {
+            return;
+        }
+        let viewport = Viewport::new(
+            self.viewport.width,
+            self.viewport.height,
+            cols,
+            rows - lines,
+        );
 
-                stdout.queue(SetForegroundColor(Color::Grey)).unwrap();
-                stdout.flush().unwrap();
+        self.buffer
+            .queue(terminal::Clear(terminal::ClearType::All))
+            .unwrap();
+        self.pos = (0, 0);
+        self.viewport = viewport;
+        self.overlay_cells = self.overlay.cells(viewport);
+        self.last_grid = vec![(' ', ContentStyle::new()); viewport.cols * (viewport.rows + lines)];
+        self.clear_history();
+
+        if let Some(msg) = self.last_frame.take() {
+            self.draw(&msg);
+            self.last_frame = Some(msg);
+            if self.max_history_bytes > 0 {
+                self.push_history();
             }
+        }
+        self.write_frame(Duration::ZERO);
+    }
 
-            RenderMessage::RenderWithStyle { new_grid } => {
-                let mut last_color = Color::Grey;
-                let mut last_background = Color::Reset;
-                let mut last_underline = Color::Reset;
-                let mut last_attributes = Attributes::none();
-
-                for y in 0..self.height {
-                    for x in 0..self.width {
-                        let c = new_grid[y * self.width + x];
-                        let c = (*c.content(), *c.style());
-                        if c != self.last_grid[y * self.width + x] {
-                            self.last_grid[y * self.width + x] = c;
-                            if let Some(fg) = c.1.foreground_color
-                                && fg != last_color
-                            {
-                                stdout.queue(SetForegroundColor(fg)).unwrap();
-                                last_color = fg;
-                            }
-                            if let Some(bg) = c.1.background_color
-                                && bg != last_background
-                            {
-                                stdout.queue(SetBackgroundColor(bg)).unwrap();
-                                last_background = bg;
-                            }
-                            if let Some(ul) = c.1.underline_color
-                                && ul != last_underline
-                            {
-                                stdout.queue(SetUnderlineColor(ul)).unwrap();
-                                last_underline = ul;
-                            }
-                            if c.1.attributes != last_attributes {
-                                stdout.queue(SetAttributes(c.1.attributes)).unwrap();
-                                last_attributes = c.1.attributes;
-                            }
-                            if last_cursor_x != x || last_cursor_y != y {
-                                stdout
-                                    .queue(cursor::MoveTo(
-                                        self.pos.0 + x as u16,
-                                        self.pos.1 + y as u16,
-                                    ))
-                                    .unwrap();
-                            }
-                            stdout.queue(style::Print(c.0)).unwrap();
-                            last_cursor_y = y;
-                            last_cursor_x = x + 1;
-                        }
-                    }
+    /// Draw a grid in which each cell may have its own style. `cell` returns
+    /// the character and style of the cell drawn at the given column and row.
+    /// Colors that are not set are drawn with the default colors.
+    fn draw_styled(&mut self, cell: impl Fn(usize, usize) -> (char, ContentStyle)) {
+        let rows = self.rows();
+        let cols = self.viewport.cols;
+        let mut writer = CellWriter::new(self.pos);
+        for y in 0..rows {
+            for x in 0..cols {
+                let c = without_colors(cell(x, y), self.colors);
+                if c != self.last_grid[y * cols + x] {
+                    self.set_cell(&mut writer, x, y, c);
                 }
+            }
+        }
+        writer.finish(&mut self.buffer);
+    }
 
-                stdout.queue(SetForegroundColor(Color::Grey)).unwrap();
-                stdout.queue(SetBackgroundColor(Color::Reset)).unwrap();
-                stdout.queue(SetAttributes(Attributes::none())).unwrap();
+    /// Draw a cell that differs from the one drawn before at the given column
+    /// and row (and record the change for the history)
+    fn set_cell(&mut self, writer: &mut CellWriter, x: usize, y: usize, c: (char, ContentStyle)) {
+        let i = y * self.viewport.cols + x;
+        if self.max_history_bytes > 0 {
+            self.changes.push((i, self.last_grid[i], c));
+        }
+        self.last_grid[i] = c;
+        writer.write(&mut self.buffer, x, y, c);
+    }
 
-                stdout.flush().unwrap();
+    /// Apply changed cells to the most recent frame and draw only those that
+    /// are visible and differ from what is on screen. Only these cells are
+    /// recorded in the history, so a patch costs as much as its changes.
+    fn patch(&mut self, mut changes: Vec<Change>) {
+        let viewport = self.viewport;
+        let mut frame = match self.last_frame.take() {
+            Some(RenderMessage::RenderCells { new_grid }) => new_grid,
+            Some(msg) => (0..viewport.width * viewport.height)
+                .map(|i| msg.cell(i))
+                .collect(),
+            None => vec![(' ', ContentStyle::new()); viewport.width * viewport.height],
+        };
+        for &(i, c) in &changes {
+            frame[i] = c;
+        }
+        let msg = RenderMessage::RenderCells { new_grid: frame };
+
+        if viewport.is_scaled() || self.redraw {
+            // a changed cell might not be visible or it might cover several
+            // cells of the frame, and cells that the overlay has covered
+            // before might be visible now
+            self.draw(&msg);
+        } else {
+            let header = self.overlay.header.len();
+            let mut writer = CellWriter::new(self.pos);
+            for &(i, c) in &changes {
+                let x = i % viewport.width;
+                let y = i / viewport.width + header;
+                let v = y * viewport.cols + x;
+                let c = without_colors(c, self.colors);
+                if self.overlay_cells[v].is_none() && self.last_grid[v] != c {
+                    self.set_cell(&mut writer, x, y, c);
+                }
+            }
+            writer.finish(&mut self.buffer);
+        }
+        self.last_frame = Some(msg);
+
+        // give the buffer back to the screen, so it can be reused
+        changes.clear();
+        if let Some(recycle) = &self.recycle {
+            let _ = recycle.try_send(changes);
+        }
+    }
+}
+
+/// Remove the colors of a cell (but keep its attributes) unless `colors` is
+/// `true`
+fn without_colors(c: (char, ContentStyle), colors: bool) -> (char, ContentStyle) {
+    if colors {
+        return c;
+    }
+    let mut style = ContentStyle::new();
+    style.attributes = c.1.attributes;
+    (c.0, style)
+}
+
+/// Writes cells into a buffer and only queues the commands that change the
+/// style or move the cursor if necessary
+struct CellWriter {
+    pos: (u16, u16),
+    cursor: (usize, usize),
+    color: Color,
+    background: Color,
+    underline: Color,
+    attributes: Attributes,
+}
+
+impl CellWriter {
+    /// Create a writer for a grid whose top-left corner is at `pos`
+    fn new(pos: (u16, u16)) -> Self {
+        Self {
+            pos,
+            cursor: (usize::MAX, usize::MAX),
+            color: Color::Grey,
+            background: Color::Reset,
+            underline: Color::Reset,
+            attributes: Attributes::none(),
+        }
+    }
+
+    /// Queue commands that draw `c` at the given column and row
+    fn write(&mut self, buffer: &mut Vec<u8>, x: usize, y: usize, c: (char, ContentStyle)) {
+        if c.1.attributes != self.attributes {
+            if !self.attributes.is_empty() {
+                // attributes can only be removed by resetting all of them
+                // (including the colors)
+                buffer.queue(SetAttribute(Attribute::Reset)).unwrap();
+                self.color = Color::Reset;
+                self.background = Color::Reset;
+                self.underline = Color::Reset;
             }
+            buffer.queue(SetAttributes(c.1.attributes)).unwrap();
+            self.attributes = c.1.attributes;
+        }
+        let fg = c.1.foreground_color.unwrap_or(Color::Grey);
+        if fg != self.color {
+            buffer.queue(SetForegroundColor(fg)).unwrap();
+            self.color = fg;
+        }
+        let bg = c.1.background_color.unwrap_or(Color::Reset);
+        if bg != self.background {
+            buffer.queue(SetBackgroundColor(bg)).unwrap();
+            self.background = bg;
+        }
+        let ul = c.1.underline_color.unwrap_or(Color::Reset);
+        if ul != self.underline {
+            buffer.queue(SetUnderlineColor(ul)).unwrap();
+            self.underline = ul;
+        }
+        if self.cursor != (x, y) {
+            buffer
+                .queue(cursor::MoveTo(self.pos.0 + x as u16, self.pos.1 + y as u16))
+                .unwrap();
+        }
+        buffer.queue(style::Print(c.0)).unwrap();
+        self.cursor = (x + 1, y);
+    }
+
+    /// Restore the default style (if necessary)
+    fn finish(mut self, buffer: &mut Vec<u8>) {
+        if !self.attributes.is_empty() {
+            buffer.queue(SetAttribute(Attribute::Reset)).unwrap();
+            self.color = Color::Reset;
+            self.background = Color::Reset;
+            self.underline = Color::Reset;
+        }
+        if self.color != Color::Grey {
+            buffer.queue(SetForegroundColor(Color::Grey)).unwrap();
+        }
+        if self.background != Color::Reset {
+            buffer.queue(SetBackgroundColor(Color::Reset)).unwrap();
+        }
+        if self.underline != Color::Reset {
+            buffer.queue(SetUnderlineColor(Color::Reset)).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use crossbeam_channel::{Receiver, bounded, unbounded};
+    use crossterm::style::ContentStyle;
+
+    use super::{
+        HistoryChange, MAX_HISTORY_BYTES, RESIZE_POLL_INTERVAL, RenderMessage, RenderTarget,
+        Renderer, TIME_PER_FRAME_RANGE,
+    };
+    use crate::{controls::Control, overlay::Overlay, viewport::Viewport};
+
+    /// Create a renderer for frames with 2x2 cells that records them into a
+    /// temporary file and keeps a history
+    fn renderer(name: &str) -> Renderer {
+        let path = env::temp_dir().join(format!("screen_test_renderer_{name}.cast"));
+        let target = RenderTarget::asciicast(&path, 2, 2).unwrap();
+        let mut renderer = Renderer::new(
+            Viewport::new(2, 2, 2, 2),
+            Overlay::new(0, 0),
+            Duration::from_millis(100),
+            (0, 0),
+            target,
+            None,
+        );
+        renderer.max_history_bytes = MAX_HISTORY_BYTES;
+        renderer
+    }
+
+    fn frame(c: char) -> RenderMessage {
+        RenderMessage::Render {
+            new_grid: vec![c; 4],
         }
     }
+
+    /// Get the character of the frame that is currently drawn
+    fn shown(renderer: &Renderer) -> char {
+        renderer.last_grid[0].0
+    }
+
+    #[test]
+    fn test_step_through_history() {
+        let mut renderer = renderer("history");
+        for c in ['a', 'b', 'c'] {
+            renderer.render(frame(c), 0);
+        }
+        let (sender, receiver) = unbounded();
+        let control = |renderer: &mut Renderer, control, receiver: &Receiver<_>| {
+            renderer.handle_control(control, receiver);
+            (shown(renderer), renderer.paused, renderer.history_pos)
+        };
+
+        // stepping back stops at the oldest frame
+        assert_eq!(
+            control(&mut renderer, Control::StepBack, &receiver),
+            ('b', true, Some(1))
+        );
+        assert_eq!(
+            control(&mut renderer, Control::StepBack, &receiver),
+            ('a', true, Some(0))
+        );
+        assert_eq!(
+            control(&mut renderer, Control::StepBack, &receiver),
+            ('a', true, Some(0))
+        );
+
+        // stepping forward leaves the history at the latest frame
+        assert_eq!(
+            control(&mut renderer, Control::StepForward, &receiver),
+            ('b', true, Some(1))
+        );
+        assert_eq!(
+            control(&mut renderer, Control::StepForward, &receiver),
+            ('c', true, None)
+        );
+
+        // and then renders the next frame (skipping other messages)
+        sender.send(RenderMessage::Colors(true)).unwrap();
+        sender.send(frame('d')).unwrap();
+        sender.send(frame('e')).unwrap();
+        assert_eq!(
+            control(&mut renderer, Control::StepForward, &receiver),
+            ('d', true, None)
+        );
+        assert_eq!(renderer.history.len(), 4);
+        assert_eq!(receiver.len(), 1);
+
+        // resuming shows the latest frame again
+        control(&mut renderer, Control::StepBack, &receiver);
+        assert_eq!(
+            control(&mut renderer, Control::TogglePause, &receiver),
+            ('d', false, None)
+        );
+        assert_eq!(
+            control(&mut renderer, Control::TogglePause, &receiver),
+            ('d', true, None)
+        );
+    }
+
+    #[test]
+    fn test_history_limit() {
+        // room for the changes of two frames with 4 cells each
+        let mut renderer = renderer("limit");
+        renderer.max_history_bytes = 8 * size_of::<HistoryChange>();
+        for c in ['a', 'b', 'c'] {
+            renderer.render(frame(c), 0);
+        }
+        assert_eq!(renderer.history.len(), 2);
+        assert_eq!(renderer.history_bytes, renderer.max_history_bytes);
+
+        let (_sender, receiver) = unbounded();
+        renderer.handle_control(Control::StepBack, &receiver);
+        renderer.handle_control(Control::StepBack, &receiver);
+        assert_eq!(shown(&renderer), 'b');
+    }
+
+    #[test]
+    fn test_patch_history() {
+        let mut renderer = renderer("patch");
+        renderer.render(frame('a'), 0);
+        let bytes = renderer.history_bytes;
+        let changes = vec![(3, ('x', ContentStyle::new()))];
+        renderer.render(RenderMessage::Patch(changes), 0);
+
+        // only the changed cell is kept for the patched frame
+        let a = ('a', ContentStyle::new());
+        assert_eq!(renderer.history.len(), 2);
+        assert_eq!(
+            renderer.history[1],
+            vec![(3, a, ('x', ContentStyle::new()))]
+        );
+        assert_eq!(renderer.history_bytes - bytes, size_of::<HistoryChange>());
+
+        let (_sender, receiver) = unbounded();
+        renderer.handle_control(Control::StepBack, &receiver);
+        assert_eq!(renderer.last_grid[3], a);
+        renderer.handle_control(Control::StepForward, &receiver);
+        assert_eq!(renderer.last_grid[3].0, 'x');
+    }
+
+    #[test]
+    fn test_speed_and_quit() {
+        let mut renderer = renderer("quit");
+        let (_sender, receiver) = unbounded();
+        for _ in 0..20 {
+            renderer.handle_control(Control::Faster, &receiver);
+        }
+        assert_eq!(renderer.time_per_frame, TIME_PER_FRAME_RANGE.0);
+        for _ in 0..20 {
+            renderer.handle_control(Control::Slower, &receiver);
+        }
+        assert_eq!(renderer.time_per_frame, TIME_PER_FRAME_RANGE.1);
+
+        // after quitting, frames are not drawn anymore
+        renderer.render(frame('a'), 0);
+        renderer.handle_control(Control::TogglePause, &receiver);
+        renderer.handle_control(Control::Quit, &receiver);
+        assert!(matches!(renderer.target, RenderTarget::Discard));
+        assert!(!renderer.paused);
+        assert!(renderer.history.is_empty());
+        renderer.render(frame('b'), 0);
+        assert_eq!(shown(&renderer), 'a');
+    }
+
+    #[test]
+    fn test_finish_while_paused() {
+        let mut renderer = renderer("paused");
+        renderer.paused = true;
+        let (sender, receiver) = bounded(10);
+        let (control_sender, controls) = unbounded();
+        let (done_sender, done) = bounded(1);
+        thread::spawn(move || {
+            renderer.run(receiver, Some(controls));
+            done_sender.send(()).unwrap();
+        });
+
+        sender.send(frame('a')).unwrap();
+        drop(sender);
+        assert!(done.recv_timeout(Duration::from_millis(200)).is_err());
+
+        // closing the controls finishes the paused renderer
+        drop(control_sender);
+        done.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    #[test]
+    fn test_resize_without_controls() {
+        let mut renderer = renderer("resize");
+        let size = Arc::new(Mutex::new(Some((2, 2))));
+        let terminal_size = Arc::clone(&size);
+        renderer.poll_size(move || *terminal_size.lock().unwrap());
+
+        let (sender, receiver) = bounded(10);
+        let handle = thread::spawn(move || renderer.run(receiver, None));
+        sender.send(frame('a')).unwrap();
+        *size.lock().unwrap() = Some((4, 3));
+        thread::sleep(RESIZE_POLL_INTERVAL * 3);
+        drop(sender);
+        handle.join().unwrap();
+
+        // the terminal is cleared and the frame is drawn again
+        let path = env::temp_dir().join("screen_test_renderer_resize.cast");
+        let recording = fs::read_to_string(path).unwrap();
+        assert_eq!(recording.matches("aa").count(), 4);
+        assert!(recording.contains("\\u001b[2J"));
+    }
 }
@@ -0,0 +1,47 @@
+use std::{
+    io::{Write, stdout},
+    panic,
+    sync::{
+        Once,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crossterm::{QueueableCommand, cursor, style::ResetColor, terminal};
+
+/// Number of screens that currently draw to the terminal
+static ACTIVE_SCREENS: AtomicUsize = AtomicUsize::new(0);
+
+/// Register a screen that draws to the terminal. The first call installs a
+/// panic hook that restores the terminal while any screen is registered, so
+/// the cursor is visible and raw mode is disabled before the panic message is
+/// printed.
+pub(crate) fn register() {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ACTIVE_SCREENS.load(Ordering::SeqCst) > 0 {
+                restore_terminal();
+            }
+            previous_hook(info);
+        }));
+    });
+    ACTIVE_SCREENS.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Unregister a screen after it has restored the terminal itself
+pub(crate) fn unregister() {
+    ACTIVE_SCREENS.fetch_sub(1, Ordering::SeqCst);
+}
+
+/// Disable raw mode, reset colors, show the cursor, and start a new line.
+/// Errors are ignored because this is called while panicking.
+fn restore_terminal() {
+    let _ = terminal::disable_raw_mode();
+    let mut stdout = stdout().lock();
+    let _ = stdout.queue(ResetColor);
+    let _ = stdout.queue(cursor::Show);
+    let _ = stdout.write_all(b"\n");
+    let _ = stdout.flush();
+}
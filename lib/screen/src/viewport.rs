@@ -0,0 +1,65 @@
+/// Maps the cells of a frame to the area it is drawn to. If the area is
+/// smaller than the frame, the frame is downscaled by sampling the nearest
+/// cell.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Viewport {
+    /// The size of the frames
+    pub width: usize,
+    pub height: usize,
+
+    /// The size of the area the frames are drawn to
+    pub cols: usize,
+    pub rows: usize,
+}
+
+impl Viewport {
+    /// Create a viewport for frames with `width * height` cells that fits
+    /// into an area with the given number of columns and rows
+    pub fn new(width: usize, height: usize, cols: usize, rows: usize) -> Self {
+        Self {
+            width,
+            height,
+            cols: width.min(cols),
+            rows: height.min(rows),
+        }
+    }
+
+    /// Check if the frames have to be downscaled
+    pub fn is_scaled(&self) -> bool {
+        self.cols != self.width || self.rows != self.height
+    }
+
+    /// Get the index of the frame cell that is drawn at the given column and
+    /// row
+    pub fn source(&self, col: usize, row: usize) -> usize {
+        if self.is_scaled() {
+            let x = col * self.width / self.cols;
+            let y = row * self.height / self.rows;
+            y * self.width + x
+        } else {
+            row * self.width + col
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Viewport;
+
+    #[test]
+    fn test_fits() {
+        let v = Viewport::new(10, 5, 80, 24);
+        assert!(!v.is_scaled());
+        assert_eq!((v.cols, v.rows), (10, 5));
+        assert_eq!(v.source(3, 2), 23);
+    }
+
+    #[test]
+    fn test_downscaled() {
+        let v = Viewport::new(100, 50, 20, 10);
+        assert!(v.is_scaled());
+        assert_eq!((v.cols, v.rows), (20, 10));
+        assert_eq!(v.source(0, 0), 0);
+        assert_eq!(v.source(19, 9), 45 * 100 + 95);
+    }
+}
@@ -0,0 +1,139 @@
+use crossterm::style::Stylize;
+
+use crate::overlay::Text;
+
+/// Copy the cells of `window` (`(min_x, min_y, max_x, max_y)`) from a grid
+/// with rows of `width` cells into a buffer of `cols * rows` cells. With a
+/// `zoom` greater than 1, each cell of the buffer shows a block of
+/// `zoom * zoom` grid cells: the cell that occurs most often in the block
+/// (ignoring `blank` cells, so thin walls and paths do not disappear). Cells
+/// of the buffer outside the window are `blank`.
+pub(crate) fn zoomed_window<T: Copy + PartialEq>(
+    grid: &[T],
+    width: usize,
+    (min_x, min_y, max_x, max_y): (usize, usize, usize, usize),
+    zoom: usize,
+    (cols, rows): (usize, usize),
+    blank: T,
+) -> Vec<T> {
+    let mut result = vec![blank; cols * rows];
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for y in 0..(max_y - min_y).div_ceil(zoom).min(rows) {
+        for x in 0..(max_x - min_x).div_ceil(zoom).min(cols) {
+            let block_x = min_x + x * zoom;
+            let block_y = min_y + y * zoom;
+            if zoom == 1 {
+                result[y * cols + x] = grid[block_y * width + block_x];
+                continue;
+            }
+
+            counts.clear();
+            for gy in block_y..(block_y + zoom).min(max_y) {
+                for gx in block_x..(block_x + zoom).min(max_x) {
+                    let c = grid[gy * width + gx];
+                    if c == blank {
+                        continue;
+                    }
+                    match counts.iter_mut().find(|(other, _)| *other == c) {
+                        Some((_, n)) => *n += 1,
+                        None => counts.push((c, 1)),
+                    }
+                }
+            }
+
+            // prefer the cell that was found first if there is a tie
+            let mut best: Option<(T, usize)> = None;
+            for &(c, n) in &counts {
+                if best.is_none_or(|(_, m)| n > m) {
+                    best = Some((c, n));
+                }
+            }
+            if let Some((c, _)) = best {
+                result[y * cols + x] = c;
+            }
+        }
+    }
+    result
+}
+
+/// Get the number of columns and rows of a minimap (without its border) for
+/// a grid with size `width * height` that is drawn over a window with
+/// `cols * rows` cells. Terminal cells are about twice as high as wide, so
+/// the minimap has half as many rows as the grid's aspect ratio suggests.
+pub(crate) fn minimap_size(
+    width: usize,
+    height: usize,
+    cols: usize,
+    rows: usize,
+) -> (usize, usize) {
+    let map_cols = (cols / 4).clamp(1, 24);
+    let map_rows = (map_cols * height).div_ceil(width.max(1) * 2);
+    (map_cols, map_rows.clamp(1, (rows / 3).max(1)))
+}
+
+/// Draw a minimap with `map_cols * map_rows` cells and a border that shows
+/// the position of `window` (`(min_x, min_y, max_x, max_y)`) in a grid with
+/// size `width * height`
+pub(crate) fn minimap(
+    width: usize,
+    height: usize,
+    (min_x, min_y, max_x, max_y): (usize, usize, usize, usize),
+    (map_cols, map_rows): (usize, usize),
+) -> Text {
+    let border = |s: String| Text::from(s.dark_grey());
+    let mut result = border(format!("┌{}┐\n", "─".repeat(map_cols)));
+    for my in 0..map_rows {
+        // the range of grid rows covered by this row of the minimap
+        let y0 = my * height / map_rows;
+        let y1 = ((my + 1) * height).div_ceil(map_rows);
+        result = result.append(border("│".to_string()));
+        for mx in 0..map_cols {
+            let x0 = mx * width / map_cols;
+            let x1 = ((mx + 1) * width).div_ceil(map_cols);
+            if x0 < max_x && min_x < x1 && y0 < max_y && min_y < y1 {
+                result = result.append('█'.grey());
+            } else {
+                result = result.append('·'.dark_grey());
+            }
+        }
+        result = result.append(border("│\n".to_string()));
+    }
+    result.append(border(format!("└{}┘", "─".repeat(map_cols))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{minimap, minimap_size, zoomed_window};
+
+    #[test]
+    fn test_zoom() {
+        #[rustfmt::skip]
+        let grid = [
+            '#', ' ', ' ', '~', '~',
+            ' ', ' ', '~', '~', '#',
+            ' ', ' ', ' ', ' ', ' ',
+        ];
+
+        // no zoom
+        let window = zoomed_window(&grid, 5, (1, 0, 4, 2), 1, (3, 2), ' ');
+        assert_eq!(window, vec![' ', ' ', '~', ' ', '~', '~']);
+
+        // blank cells are ignored, ties are resolved in favor of the first
+        // cell, and incomplete blocks at the edges count, too
+        let window = zoomed_window(&grid, 5, (0, 0, 5, 3), 2, (4, 2), ' ');
+        assert_eq!(window, vec!['#', '~', '~', ' ', ' ', ' ', ' ', ' ']);
+    }
+
+    #[test]
+    fn test_minimap_window() {
+        assert_eq!(minimap_size(100, 100, 80, 24), (20, 8));
+        assert_eq!(minimap_size(1000, 10, 80, 24), (20, 1));
+
+        let lines = minimap(8, 4, (2, 0, 6, 2), (4, 2))
+            .lines()
+            .into_iter()
+            .map(|l| l.into_iter().map(|c| c.0).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["┌────┐", "│·██·│", "│····│", "└────┘"]);
+    }
+}
@@ -1,14 +1,24 @@
 #![allow(unused)]
 use grid::*;
+use input::parse::*;
+use input::*;
+use runner::*;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
-use std::fs;
+use std::process::ExitCode;
 use util::*;
 
 mod grid;
+mod output;
+mod runner;
 mod util;
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Could not read file");
+fn solve(input: &str) -> Result<(u64, u64), InputError> {
     let lines = input.lines().collect::<Vec<_>>();
-    // let grid = grid::read_to_grid("input.txt").expect("Could not read file");
+    // let grid = input.to_grid();
+
+    Ok((0, 0))
+}
+
+fn main() -> ExitCode {
+    run("input.txt", solve)
 }
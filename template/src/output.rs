@@ -0,0 +1,100 @@
+#![allow(unused)]
+use std::{env, fmt::Write, path::Path, time::Duration};
+
+/// Get the year and day of the puzzle from the name of the project directory
+/// (e.g. `2024/day17`). Returns `None` if the directory does not follow this
+/// naming scheme (e.g. for the template itself).
+pub fn puzzle_id() -> Option<(u32, u32)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let day = dir
+        .file_name()?
+        .to_str()?
+        .strip_prefix("day")?
+        .parse()
+        .ok()?;
+    let year = dir.parent()?.file_name()?.to_str()?.parse().ok()?;
+    Some((year, day))
+}
+
+/// `true` if the answers should be printed as JSON records (i.e. if the
+/// environment variable `AOC_OUTPUT` is set to `json`)
+pub fn json_output() -> bool {
+    env::var("AOC_OUTPUT").is_ok_and(|v| v == "json")
+}
+
+/// Convert a string to a JSON string literal
+pub fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Create a JSON record for the answer of the given `part`. The record has the
+/// same format as the ones printed by `cargo run-aoc --json`.
+pub fn json_record(
+    puzzle_id: Option<(u32, u32)>,
+    part: usize,
+    answer: &str,
+    elapsed: Duration,
+) -> String {
+    let (year, day) = match puzzle_id {
+        Some((year, day)) => (year.to_string(), day.to_string()),
+        None => ("null".to_string(), "null".to_string()),
+    };
+    format!(
+        r#"{{"year":{year},"day":{day},"part":{part},"answer":{},"elapsed_ns":{}}}"#,
+        json_string(answer),
+        elapsed.as_nanos()
+    )
+}
+
+/// Print answers either as plain lines or as JSON records (see
+/// [json_output])
+pub fn print_answers(answers: &[String], elapsed: Duration) {
+    if json_output() {
+        let id = puzzle_id();
+        for (i, a) in answers.iter().enumerate() {
+            println!("{}", json_record(id, i + 1, a, elapsed));
+        }
+    } else {
+        for a in answers {
+            println!("{a}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("42"), r#""42""#);
+        assert_eq!(json_string("a\"b\\c\nd"), r#""a\"b\\c\nd""#);
+        assert_eq!(json_string("\u{1}█"), r#""\u0001█""#);
+    }
+
+    #[test]
+    fn test_json_record() {
+        assert_eq!(
+            json_record(Some((2024, 17)), 1, "4,6,3", Duration::from_micros(12)),
+            r#"{"year":2024,"day":17,"part":1,"answer":"4,6,3","elapsed_ns":12000}"#
+        );
+        assert_eq!(
+            json_record(None, 2, "1", Duration::ZERO),
+            r#"{"year":null,"day":null,"part":2,"answer":"1","elapsed_ns":0}"#
+        );
+    }
+}
@@ -0,0 +1,44 @@
+#![allow(unused)]
+use std::{fmt::Display, process::ExitCode, time::Instant};
+
+use input::{InputError, input_path, read_input};
+
+use crate::output::print_answers;
+
+/// The answers of a solution. Implemented for tuples with one element (e.g.
+/// for the last day, which only has one part) or two elements.
+pub trait Answers {
+    fn answers(&self) -> Vec<String>;
+}
+
+impl<A: Display> Answers for (A,) {
+    fn answers(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+impl<A: Display, B: Display> Answers for (A, B) {
+    fn answers(&self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string()]
+    }
+}
+
+/// Read the input (see [input_path] and [read_input]), call `solve`, and
+/// print the answers (one per line or as JSON records, see [print_answers]).
+/// Print errors to stderr instead of panicking.
+pub fn run<A: Answers>(
+    default_path: &str,
+    solve: impl FnOnce(&str) -> Result<A, InputError>,
+) -> ExitCode {
+    let start = Instant::now();
+    match read_input(input_path(default_path)).and_then(|input| solve(&input)) {
+        Ok(answers) => {
+            print_answers(&answers.answers(), start.elapsed());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}